@@ -0,0 +1,123 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::color::contrast::{linear_to_srgb, srgb_to_linear};
+use crate::util::errors::FgdbParseError;
+use rgb::RGB8 as Rgb8;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+use strum_macros::EnumIter;
+
+// Dichromacy simulation matrices (severity 1.0) from Machado, Oliveira and Fernandes, "A Physiologically-based Model
+// for Simulation of Color Vision Deficiency", 2009.  The matrices operate on linear RGB.
+const PROTANOPIA: [[f64; 3]; 3] = [
+    [0.152286, 1.052583, -0.204868],
+    [0.114503, 0.786281, 0.099216],
+    [-0.003882, -0.048116, 1.051998],
+];
+const DEUTERANOPIA: [[f64; 3]; 3] = [[0.367322, 0.860646, -0.227968], [0.280085, 0.672501, 0.047413], [-0.011820, 0.042940, 0.968881]];
+const TRITANOPIA: [[f64; 3]; 3] = [
+    [1.255528, -0.076749, -0.178779],
+    [-0.078411, 0.930809, 0.147602],
+    [0.004733, 0.691367, 0.303900],
+];
+
+/// The color vision used when checking readability.  Normal vision leaves colors unchanged; the remaining variants
+/// simulate the three forms of dichromacy.
+#[derive(Clone, Copy, Debug, Default, Deserialize, EnumIter, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum ColorVision {
+    #[default]
+    Normal,
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl Display for ColorVision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ColorVision::Normal => "Normal",
+                ColorVision::Protanopia => "Protanopia",
+                ColorVision::Deuteranopia => "Deuteranopia",
+                ColorVision::Tritanopia => "Tritanopia",
+            }
+        )
+    }
+}
+
+impl FromStr for ColorVision {
+    type Err = FgdbParseError;
+    fn from_str(s: &str) -> Result<ColorVision, FgdbParseError> {
+        match s {
+            "Normal" => Ok(ColorVision::Normal),
+            "Protanopia" => Ok(ColorVision::Protanopia),
+            "Deuteranopia" => Ok(ColorVision::Deuteranopia),
+            "Tritanopia" => Ok(ColorVision::Tritanopia),
+            _ => Err(FgdbParseError::InvalidColorVision(s.to_string())),
+        }
+    }
+}
+
+impl ColorVision {
+    /// Returns color as it would be perceived with this color vision.
+    pub fn simulate(&self, color: Rgb8) -> Rgb8 {
+        let matrix = match self {
+            ColorVision::Normal => return color,
+            ColorVision::Protanopia => &PROTANOPIA,
+            ColorVision::Deuteranopia => &DEUTERANOPIA,
+            ColorVision::Tritanopia => &TRITANOPIA,
+        };
+        let linear = [srgb_to_linear(color.r), srgb_to_linear(color.g), srgb_to_linear(color.b)];
+        let apply = |row: &[f64; 3]| linear_to_srgb(row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2]);
+        Rgb8::new(apply(&matrix[0]), apply(&matrix[1]), apply(&matrix[2]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_from_str_with_valid_input_works() {
+        assert_eq!(ColorVision::from_str("Normal").unwrap(), ColorVision::Normal);
+        assert_eq!(ColorVision::from_str("Protanopia").unwrap(), ColorVision::Protanopia);
+        assert_eq!(ColorVision::from_str("Deuteranopia").unwrap(), ColorVision::Deuteranopia);
+        assert_eq!(ColorVision::from_str("Tritanopia").unwrap(), ColorVision::Tritanopia);
+    }
+
+    #[test]
+    fn test_from_str_with_invalid_input_yields_parse_error_invalid_color_vision() {
+        assert!(matches!(ColorVision::from_str("protanopia"), Err(FgdbParseError::InvalidColorVision(_))));
+        assert!(matches!(ColorVision::from_str("Achromatopsia"), Err(FgdbParseError::InvalidColorVision(_))));
+    }
+
+    #[test]
+    fn test_simulate_preserves_greys() {
+        for vision in ColorVision::iter() {
+            for level in [0u8, 64, 128, 200, 255] {
+                let grey = Rgb8::new(level, level, level);
+                let simulated = vision.simulate(grey);
+                for channel in [simulated.r, simulated.g, simulated.b] {
+                    assert!(channel.abs_diff(level) <= 1, "{} altered grey {} to {:?}.", vision, level, simulated);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_simulate_confuses_red_and_green_for_protanopia() {
+        let red = Rgb8::new(255, 0, 0);
+        let green = Rgb8::new(0, 255, 0);
+        let normal_difference = red.r.abs_diff(green.r);
+        let simulated_red = ColorVision::Protanopia.simulate(red);
+        let simulated_green = ColorVision::Protanopia.simulate(green);
+        assert!(simulated_red.r.abs_diff(simulated_green.r) < normal_difference);
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use rgb::RGB8 as Rgb8;
+use rgb::RGBA8 as Rgba8;
+
+// The color drawn behind a drop's label.  Label backgrounds are blended over the game world, which for the purpose of
+// readability checks is assumed to be dark.
+pub const DEFAULT_GROUND: Rgb8 = Rgb8::new(0, 0, 0);
+
+// WCAG 2 minimum contrast ratio for normal text (level AA).
+pub const WCAG_AA_CONTRAST_RATIO: f64 = 4.5;
+
+/// Converts an 8-bit sRGB channel to linear light in the range [0-1].
+pub fn srgb_to_linear(channel: u8) -> f64 {
+    let c = channel as f64 / 255.0;
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+/// Converts a linear light value to an 8-bit sRGB channel.  Values outside [0-1] are clamped.
+pub fn linear_to_srgb(value: f64) -> u8 {
+    let v = value.clamp(0.0, 1.0);
+    let c = if v <= 0.0031308 { v * 12.92 } else { 1.055 * v.powf(1.0 / 2.4) - 0.055 };
+    (c * 255.0).round() as u8
+}
+
+/// Returns the WCAG relative luminance of color in the range [0-1].
+pub fn relative_luminance(color: Rgb8) -> f64 {
+    0.2126 * srgb_to_linear(color.r) + 0.7152 * srgb_to_linear(color.g) + 0.0722 * srgb_to_linear(color.b)
+}
+
+/// Returns the WCAG contrast ratio between two colors in the range [1-21].  The order of the arguments is immaterial.
+pub fn contrast_ratio(first: Rgb8, second: Rgb8) -> f64 {
+    let first_luminance = relative_luminance(first);
+    let second_luminance = relative_luminance(second);
+    let (lighter, darker) = if first_luminance >= second_luminance {
+        (first_luminance, second_luminance)
+    } else {
+        (second_luminance, first_luminance)
+    };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Blends a possibly translucent color over an opaque ground color, returning the color seen on screen.
+pub fn composite(color: Rgba8, ground: Rgb8) -> Rgb8 {
+    let alpha = color.a as f64 / 255.0;
+    let blend = |c: u8, g: u8| (alpha * c as f64 + (1.0 - alpha) * g as f64).round() as u8;
+    Rgb8::new(blend(color.r, ground.r), blend(color.g, ground.g), blend(color.b, ground.b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Rgb8 = Rgb8::new(0, 0, 0);
+    const WHITE: Rgb8 = Rgb8::new(255, 255, 255);
+
+    #[test]
+    fn test_contrast_ratio_works() {
+        assert!((contrast_ratio(WHITE, BLACK) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio(BLACK, WHITE) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio(WHITE, WHITE) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_composite_works() {
+        assert_eq!(WHITE, composite(Rgba8::new(255, 255, 255, 255), BLACK));
+        assert_eq!(BLACK, composite(Rgba8::new(255, 255, 255, 0), BLACK));
+        assert_eq!(Rgb8::new(128, 0, 0), composite(Rgba8::new(255, 0, 0, 128), BLACK));
+    }
+
+    #[test]
+    fn test_linear_round_trip_works() {
+        for channel in 0..=255u8 {
+            assert_eq!(channel, linear_to_srgb(srgb_to_linear(channel)));
+        }
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::color::color_vision::ColorVision;
+use crate::color::contrast;
+use crate::color::contrast::{DEFAULT_GROUND, WCAG_AA_CONTRAST_RATIO};
+use crate::db::tx::Tx;
+use crate::types::stock_color::StockColor;
+use crate::util::errors::FgdbError;
+use rgb::RGB8 as Rgb8;
+use rgb::RGBA8 as Rgba8;
+use rusqlite::Error as RusqliteError;
+
+// An action set whose text is insufficiently distinguishable from its background.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContrastViolation {
+    // The name of the action set.
+    pub action_set: String,
+
+    // The stock color used for the action set's text.
+    pub text_color: StockColor,
+
+    // The name of the RGBA color used for the action set's background.
+    pub background_color: String,
+
+    // The contrast ratio between text and background as perceived with the checker's color vision.
+    pub contrast_ratio: f64,
+}
+
+/// Reports action sets whose text color and background color combine to produce an unreadable drop label.
+#[derive(Clone, Copy, Debug)]
+pub struct ContrastChecker {
+    threshold: f64,
+    ground: Rgb8,
+    vision: ColorVision,
+}
+
+impl Default for ContrastChecker {
+    fn default() -> Self {
+        ContrastChecker::builder().build()
+    }
+}
+
+impl ContrastChecker {
+    pub fn builder() -> ContrastCheckerBuilder {
+        ContrastCheckerBuilder {
+            threshold: WCAG_AA_CONTRAST_RATIO,
+            ground: DEFAULT_GROUND,
+            vision: ColorVision::Normal,
+        }
+    }
+
+    /// Returns every action set having a contrast ratio below the checker's threshold, ordered by action set name.
+    /// Action sets whose background color is missing from the colors table are not checked.
+    pub fn check(&self, tx: &mut Tx) -> Result<Vec<ContrastViolation>, FgdbError> {
+        let mut stmt = tx.prepare(
+            "SELECT a.action_set, a.text_color, a.background_color, c.red, c.green, c.blue, c.alpha
+             FROM action_sets a
+             JOIN colors c ON a.background_color = c.color
+             ORDER BY a.action_set",
+        )?;
+        let candidates = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<usize, String>(0)?,
+                    row.get::<usize, StockColor>(1)?,
+                    row.get::<usize, String>(2)?,
+                    Rgba8::new(row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?),
+                ))
+            })?
+            .collect::<Result<Vec<_>, RusqliteError>>()?;
+
+        Ok(candidates
+            .into_iter()
+            .map(|(action_set, text_color, background_color, pixel)| ContrastViolation {
+                contrast_ratio: self.contrast_ratio(text_color, pixel),
+                action_set,
+                text_color,
+                background_color,
+            })
+            .filter(|violation| violation.contrast_ratio < self.threshold)
+            .collect())
+    }
+
+    /// Returns the contrast ratio between a stock text color and an RGBA background as perceived with the checker's
+    /// color vision.  The background is first blended over the checker's ground color.
+    pub fn contrast_ratio(&self, text_color: StockColor, background: Rgba8) -> f64 {
+        let text = self.vision.simulate(text_color.rgb());
+        let background = self.vision.simulate(contrast::composite(background, self.ground));
+        contrast::contrast_ratio(text, background)
+    }
+
+    pub fn ground(&self) -> Rgb8 {
+        self.ground
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    pub fn vision(&self) -> ColorVision {
+        self.vision
+    }
+}
+
+// Builder for ContrastChecker.
+#[derive(Debug)]
+pub struct ContrastCheckerBuilder {
+    threshold: f64,
+
+    ground: Rgb8,
+
+    vision: ColorVision,
+}
+
+impl ContrastCheckerBuilder {
+    pub fn build(self) -> ContrastChecker {
+        ContrastChecker {
+            threshold: self.threshold,
+            ground: self.ground,
+            vision: self.vision,
+        }
+    }
+
+    pub fn ground(mut self, ground: Rgb8) -> ContrastCheckerBuilder {
+        self.ground = ground;
+        self
+    }
+
+    pub fn threshold(mut self, threshold: f64) -> ContrastCheckerBuilder {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn vision(mut self, vision: ColorVision) -> ContrastCheckerBuilder {
+        self.vision = vision;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use crate::db::conn::Conn;
+    use rusqlite::params;
+
+    fn insert_action_set(tx: &mut Tx, action_set: &str, text_color: StockColor, background_color: &str) {
+        tx.execute(
+            "INSERT INTO action_sets (action_set, is_template_compatible, text_color, border_color, background_color) VALUES (?1, 0, ?2, ?2, ?3)",
+            params![action_set, text_color, background_color],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_contrast_ratio_accounts_for_alpha() {
+        let checker = ContrastChecker::default();
+        let opaque_white = Rgba8::new(255, 255, 255, 255);
+        let transparent_white = Rgba8::new(255, 255, 255, 0);
+        assert!(checker.contrast_ratio(StockColor::White, opaque_white) < 1.01);
+        assert!(checker.contrast_ratio(StockColor::White, transparent_white) > 20.9);
+    }
+
+    #[test]
+    fn test_check_reports_action_sets_below_threshold() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        insert_action_set(&mut tx, "Readable", StockColor::White, "Black");
+        insert_action_set(&mut tx, "Unreadable", StockColor::White, "White");
+
+        let violations = ContrastChecker::default().check(&mut tx).unwrap();
+        assert!(violations.iter().any(|v| v.action_set == "Unreadable" && v.background_color == "White"));
+        assert!(!violations.iter().any(|v| v.action_set == "Readable"));
+
+        let violations = ContrastChecker::builder().threshold(1.0).build().check(&mut tx).unwrap();
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_check_applies_color_vision() {
+        let red_on_green = Rgba8::new(0, 160, 0, 255);
+        let normal = ContrastChecker::default().contrast_ratio(StockColor::Red, red_on_green);
+        let deuteranopia = ContrastChecker::builder()
+            .vision(ColorVision::Deuteranopia)
+            .build()
+            .contrast_ratio(StockColor::Red, red_on_green);
+        assert_ne!(normal, deuteranopia);
+    }
+}
@@ -0,0 +1,7 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+pub mod color_vision;
+pub mod contrast;
+pub mod contrast_checker;
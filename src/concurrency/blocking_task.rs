@@ -0,0 +1,200 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, LazyLock, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+// The maximum number of worker threads running blocking functions.  Functions submitted while every worker is busy wait
+// in a queue.
+const MAX_WORKERS: usize = 8;
+
+static WORKER_POOL: LazyLock<WorkerPool> = LazyLock::new(WorkerPool::default);
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+#[derive(Default)]
+struct WorkerPool {
+    state: Mutex<WorkerPoolState>,
+    job_queued: Condvar,
+}
+
+#[derive(Default)]
+struct WorkerPoolState {
+    jobs: VecDeque<Job>,
+
+    // The number of worker threads started.
+    workers: usize,
+
+    // The number of workers waiting for a job.
+    idle: usize,
+}
+
+impl WorkerPool {
+    // Queues job, starting a worker if none is idle and fewer than MAX_WORKERS are running.  If no worker is running
+    // and none can be started, job is run on the calling thread.
+    fn submit(&'static self, job: Job) {
+        let mut state = self.lock();
+        state.jobs.push_back(job);
+        if state.idle < state.jobs.len() && state.workers < MAX_WORKERS {
+            match thread::Builder::new().name("fgdb-blocking".to_string()).spawn(|| self.work()) {
+                Ok(_) => state.workers += 1,
+                Err(_) if state.workers == 0 => {
+                    let job = state.jobs.pop_back().expect("job was just queued");
+                    drop(state);
+                    job();
+                    return;
+                }
+                Err(_) => {}
+            }
+        }
+        drop(state);
+        self.job_queued.notify_one();
+    }
+
+    fn work(&self) {
+        let mut state = self.lock();
+        loop {
+            match state.jobs.pop_front() {
+                Some(job) => {
+                    drop(state);
+                    job();
+                    state = self.lock();
+                }
+                None => {
+                    state.idle += 1;
+                    state = self.job_queued.wait(state).unwrap_or_else(PoisonError::into_inner);
+                    state.idle -= 1;
+                }
+            }
+        }
+    }
+
+    // Jobs never run while the lock is held, so the state remains consistent even if the lock has been poisoned.
+    fn lock(&self) -> MutexGuard<'_, WorkerPoolState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// The outcome of the blocking function together with the waker of the task awaiting it.
+struct Shared<T> {
+    result: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+/// A future which completes with the result of a blocking function run on a bounded pool of worker threads.  Because
+/// the work does not run on the executor, a BlockingTask may be awaited from any async runtime, e.g., tokio, without
+/// stalling it.  If the function panics, the panic is resumed in the task awaiting the future.  Dropping the future does
+/// not stop the function; pass a CancellationToken to the function for that.
+pub struct BlockingTask<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> Future for BlockingTask<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut shared = self.shared.lock().unwrap_or_else(PoisonError::into_inner);
+        match shared.result.take() {
+            Some(Ok(value)) => Poll::Ready(value),
+            Some(Err(panic)) => std::panic::resume_unwind(panic),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Runs f on the blocking worker pool, returning a future which completes with its result.  If every worker is busy, f
+/// waits until one is free.  In the unlikely event that no worker thread can be started, f is run on the calling
+/// thread before spawn_blocking returns.
+pub fn spawn_blocking<T, F>(f: F) -> BlockingTask<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let shared = Arc::new(Mutex::new(Shared { result: None, waker: None }));
+    let job_shared = Arc::clone(&shared);
+    WORKER_POOL.submit(Box::new(move || {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+        let mut shared = job_shared.lock().unwrap_or_else(PoisonError::into_inner);
+        shared.result = Some(result);
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }));
+    BlockingTask { shared }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc;
+    use std::task::Wake;
+    use std::time::Duration;
+
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    // A minimal executor which runs future to completion on the current thread.
+    pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(value) = future.as_mut().poll(&mut cx) {
+                return value;
+            }
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn test_spawn_blocking_works() {
+        let (sender, receiver) = mpsc::channel::<()>();
+        let task = spawn_blocking(move || {
+            receiver.recv_timeout(Duration::from_secs(60)).unwrap();
+            42
+        });
+        sender.send(()).unwrap();
+        assert_eq!(42, block_on(task));
+    }
+
+    #[test]
+    fn test_spawn_blocking_resumes_panic() {
+        let result = std::panic::catch_unwind(|| block_on(spawn_blocking(|| panic!("blocking failure"))));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_spawn_blocking_bounds_threads() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let most_running = Arc::new(AtomicUsize::new(0));
+        let tasks: Vec<_> = (0..MAX_WORKERS * 3)
+            .map(|i| {
+                let (running, most_running) = (Arc::clone(&running), Arc::clone(&most_running));
+                spawn_blocking(move || {
+                    most_running.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(5));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    i
+                })
+            })
+            .collect();
+        for (i, task) in tasks.into_iter().enumerate() {
+            assert_eq!(i, block_on(task));
+        }
+        assert!(most_running.load(Ordering::SeqCst) <= MAX_WORKERS);
+    }
+}
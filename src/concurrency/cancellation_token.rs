@@ -0,0 +1,82 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::util::errors::FgdbError;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// The longest time sleep waits before checking the token again.
+const SLEEP_SLICE: Duration = Duration::from_millis(50);
+
+/// Requests that a long-running operation stop.  Clones share state, so an operation given a clone is cancelled when
+/// cancel is called on any clone.  Operations check the token between steps and return FgdbError::Cancelled once it
+/// has been cancelled.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        CancellationToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Returns FgdbError::Cancelled if the token has been cancelled.
+    pub fn check(&self) -> Result<(), FgdbError> {
+        if self.is_cancelled() {
+            return Err(FgdbError::Cancelled);
+        }
+        Ok(())
+    }
+
+    /// Waits for duration, returning FgdbError::Cancelled as soon as the token is cancelled.
+    pub fn sleep(&self, duration: Duration) -> Result<(), FgdbError> {
+        let deadline = Instant::now() + duration;
+        loop {
+            self.check()?;
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(());
+            }
+            thread::sleep(remaining.min(SLEEP_SLICE));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_is_shared_by_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(clone.check().is_ok());
+        token.cancel();
+        assert!(clone.is_cancelled());
+        assert!(matches!(clone.check(), Err(FgdbError::Cancelled)));
+    }
+
+    #[test]
+    fn test_sleep_stops_when_cancelled() {
+        let token = CancellationToken::new();
+        token.sleep(Duration::from_millis(1)).unwrap();
+        let clone = token.clone();
+        let canceller = thread::spawn(move || clone.cancel());
+        let start = Instant::now();
+        assert!(matches!(token.sleep(Duration::from_secs(60)), Err(FgdbError::Cancelled)));
+        assert!(start.elapsed() < Duration::from_secs(60));
+        canceller.join().unwrap();
+    }
+}
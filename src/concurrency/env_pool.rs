@@ -2,23 +2,97 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 1/22/2026.
 
+use crate::config::fgdb_config::try_get_config;
+use crate::db::conn::Conn;
+use crate::fs::data_root::test_env_root;
+use crate::fs::dir::{Dir, ENV_DIRS};
+use crate::fs::paths::Paths;
+use crate::util::consts;
 use crate::util::env::Env;
-use std::sync::LazyLock;
-use std::sync::{Condvar, Mutex, Once};
+use crate::util::errors::FgdbError;
+use std::collections::VecDeque;
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, LazyLock, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
 
-static INIT: Once = Once::new();
-static LOCK_CV_PAIR: LazyLock<(Mutex<bool>, Condvar)> = LazyLock::new(|| (Mutex::new(true), Condvar::new()));
+// Name of the environment variable used by the test harness to set the number of test threads.
+const TEST_THREADS_ENV_VAR: &str = "RUST_TEST_THREADS";
 
-static POOL: LazyLock<Mutex<Vec<Env>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+// Name of the file locked by the process owning a test environment root.
+const LOCK_FILE_NAME: &str = ".lock";
+
+// Name under which the lock file is created and locked before it is renamed to LOCK_FILE_NAME.
+const LOCK_TEMP_FILE_NAME: &str = ".lock.tmp";
+
+// The template environment holds the database copied into a test environment when it is handed out.
+const TEMPLATE_ENV: Env = Env::Test(0);
+
+static CAPACITY: LazyLock<usize> = LazyLock::new(EnvPool::default_capacity);
+static POOL: LazyLock<(Mutex<PoolState>, Condvar)> = LazyLock::new(|| (Mutex::new(PoolState::default()), Condvar::new()));
+static TEMPLATE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+// The lock file is held open for the life of the process to mark the test environment root as in use.
+static ROOT_LOCK: OnceLock<File> = OnceLock::new();
+
+#[derive(Debug, Default)]
+struct PoolState {
+    // Environments which have been created and are not in use.
+    idle: VecDeque<Env>,
+
+    // The number of environments created.
+    created: u32,
+
+    // Tickets of waiting callers in order of arrival.  Only the caller at the front may take an environment.
+    waiting: VecDeque<u64>,
+
+    next_ticket: u64,
+}
+
+impl PoolState {
+    fn is_env_available(&self) -> bool {
+        !self.idle.is_empty() || (self.created as usize) < EnvPool::capacity()
+    }
+
+    fn take(&mut self) -> Env {
+        self.idle.pop_front().unwrap_or_else(|| {
+            self.created += 1;
+            Env::Test(self.created)
+        })
+    }
+}
 
 pub struct EnvPoolGuard {
     pub env: Env,
 }
 
 impl EnvPoolGuard {
+    /// Takes a test environment, waiting until one is available.
     pub fn new() -> Self {
         Self { env: EnvPool::get() }
     }
+
+    /// Takes a test environment if one is available without waiting.
+    pub fn try_new() -> Option<Self> {
+        EnvPool::try_get().map(|env| Self { env })
+    }
+
+    /// Takes a test environment, waiting at most timeout for one to become available.
+    pub fn with_timeout(timeout: Duration) -> Option<Self> {
+        EnvPool::get_timeout(timeout).map(|env| Self { env })
+    }
+
+    /// Returns an empty directory named test_name within the environment's out directory, removing whatever an earlier
+    /// run of the test left there.
+    pub fn out_dir(&self, test_name: &str) -> PathBuf {
+        let dir = Paths::new(self.env).lookup(Dir::EnvOut).join(test_name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
 }
 
 impl Default for EnvPoolGuard {
@@ -33,57 +107,275 @@ impl Drop for EnvPoolGuard {
     }
 }
 
+/// Hands out test environments.  Environments are created on demand in the test environment root, up to capacity, and
+/// reused once returned.  Before an environment is handed out, its output directory is emptied and its database is
+/// reset to the test data for the configured game variant.  Callers are served in order of arrival.
+///
+/// A test which panics while holding an environment does not disable the pool: the environment is returned by its guard
+/// and reset before reuse.  Test environment roots left behind by earlier processes are removed on first use.
 pub struct EnvPool;
 impl EnvPool {
-    /// By default, the EnvPool is statically initialized to use test environments.  If a different set
-    /// of environments is desired, this function may be called prior to the first use of the get/put methods.
-    /// Note that change_envs is not thread safe and cannot be called once the pool is in use.
-    pub fn change_envs(envs: impl Iterator<Item=Env>) {
-        let mut pool = POOL.lock().unwrap();
-        pool.clear();
-        pool.extend(envs);
+    /// Returns the maximum number of test environments which may exist at once.  This is the number of test threads,
+    /// as given by RUST_TEST_THREADS, or else the available parallelism.
+    pub fn capacity() -> usize {
+        *CAPACITY
     }
 
+    /// Takes a test environment, waiting until one is available.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the environment cannot be reset.
     pub fn get() -> Env {
-        EnvPool::init();
+        EnvPool::take(None).expect("waiting without a deadline always yields an environment")
+    }
+
+    /// Takes a test environment, waiting at most timeout for one to become available.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the environment cannot be reset.
+    pub fn get_timeout(timeout: Duration) -> Option<Env> {
+        EnvPool::take(Some(Instant::now() + timeout))
+    }
+
+    /// Takes a test environment if one is available and no other caller is waiting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the environment cannot be reset.
+    pub fn try_get() -> Option<Env> {
+        let env = {
+            let mut state = EnvPool::lock();
+            if !state.waiting.is_empty() || !state.is_env_available() {
+                return None;
+            }
+            state.take()
+        };
+        EnvPool::prepare(env);
+        Some(env)
+    }
+
+    /// Returns the test environment to the pool.
+    pub fn put(env: Env) {
+        let (_, cvar) = &*POOL;
+        EnvPool::lock().idle.push_back(env);
+        cvar.notify_all();
+    }
 
-        let (lock, cvar) = &*LOCK_CV_PAIR;
-        let mut is_env_available = lock.lock().unwrap();
+    fn take(deadline: Option<Instant>) -> Option<Env> {
+        let (_, cvar) = &*POOL;
+        let mut state = EnvPool::lock();
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        state.waiting.push_back(ticket);
 
-        while !*is_env_available {
-            is_env_available = cvar.wait(is_env_available).unwrap();
+        while state.waiting.front() != Some(&ticket) || !state.is_env_available() {
+            state = match deadline {
+                None => cvar.wait(state).unwrap_or_else(PoisonError::into_inner),
+                Some(deadline) => {
+                    let timeout = deadline.saturating_duration_since(Instant::now());
+                    if timeout.is_zero() {
+                        state.waiting.retain(|t| *t != ticket);
+                        cvar.notify_all();
+                        return None;
+                    }
+                    cvar.wait_timeout(state, timeout).unwrap_or_else(PoisonError::into_inner).0
+                }
+            };
         }
 
-        let mut pool = POOL.lock().unwrap();
-        let env = pool.pop().unwrap();
-        if pool.is_empty() {
-            *is_env_available = false;
-        } else {
-            cvar.notify_one();
+        state.waiting.pop_front();
+        let env = state.take();
+        drop(state);
+        cvar.notify_all();
+
+        EnvPool::prepare(env);
+        Some(env)
+    }
+
+    // Locks the pool state.  The state is updated only by the pool, which never panics while holding the lock, so the
+    // state remains consistent even if the lock has been poisoned.
+    fn lock() -> MutexGuard<'static, PoolState> {
+        POOL.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn prepare(env: Env) {
+        if let Err(e) = EnvPool::reset(env) {
+            EnvPool::put(env);
+            panic!("Unable to reset test environment {}: {}", env, e);
+        }
+    }
+
+    // Empties the environment's directories and replaces its database with a copy of the template.
+    fn reset(env: Env) -> Result<(), FgdbError> {
+        let template = EnvPool::template()?;
+        let paths = Paths::create(try_get_config()?.game_variant, env);
+        let env_dir = paths.env_dir();
+        if env_dir.exists() {
+            fs::remove_dir_all(&env_dir)?;
         }
-        env
-    }
-
-    pub fn init() {
-        // Initialize the pool
-        INIT.call_once(|| {
-            let mut pool = POOL.lock().unwrap();
-            // Check if the pool is empty before proceeding.  We don't want to override a previous call to
-            // change_envs.
-            if pool.is_empty() {
-                for env in Env::test_envs() {
-                    pool.push(env);
+        paths.create_dirs(ENV_DIRS.iter())?;
+        fs::copy(template, paths.lookup(Dir::EnvDb).join(consts::DB_NAME))?;
+        Ok(())
+    }
+
+    // Returns the path of the template database, creating it on first use.  A failure is not remembered, so the next
+    // environment handed out retries creating the template.
+    fn template() -> Result<PathBuf, FgdbError> {
+        let mut template = TEMPLATE.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(path) = &*template {
+            return Ok(path.clone());
+        }
+        let path = EnvPool::create_template()?;
+        *template = Some(path.clone());
+        Ok(path)
+    }
+
+    fn create_template() -> Result<PathBuf, FgdbError> {
+        EnvPool::lock_root()?;
+        let paths = Paths::create(try_get_config()?.game_variant, TEMPLATE_ENV);
+        let env_dir = paths.env_dir();
+        if env_dir.exists() {
+            fs::remove_dir_all(&env_dir)?;
+        }
+        paths.create_dirs(ENV_DIRS.iter())?;
+        let mut conn = Conn::with_paths(&paths)?;
+        conn.populate_test_data()?;
+        Ok(paths.lookup(Dir::EnvDb).join(consts::DB_NAME))
+    }
+
+    // Removes test environment roots whose owning process has exited, then marks this process's root as in use.  Does
+    // nothing if this process's root is already marked.
+    fn lock_root() -> Result<(), FgdbError> {
+        if ROOT_LOCK.get().is_some() {
+            return Ok(());
+        }
+        let root = test_env_root();
+        if let Ok(entries) = fs::read_dir(env::temp_dir()) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let is_test_env_root = entry.file_name().to_string_lossy().starts_with(consts::TEST_ENV_ROOT_PREFIX);
+                if is_test_env_root && path != root && EnvPool::is_abandoned(&path) {
+                    let _ = fs::remove_dir_all(&path);
                 }
             }
+        }
+
+        if root.exists() {
+            fs::remove_dir_all(root)?;
+        }
+        fs::create_dir_all(root)?;
+
+        // The lock file is locked before it is renamed into place so that other processes never find it unlocked and
+        // mistake the root for abandoned.  The lock belongs to the open file and so survives the rename.
+        let temp_path = root.join(LOCK_TEMP_FILE_NAME);
+        let file = File::create(&temp_path)?;
+        file.try_lock().map_err(|e| FgdbError::Config(format!("Unable to lock test environment root '{}': {}", root.display(), e), Some(Box::new(e))))?;
+        fs::rename(&temp_path, root.join(LOCK_FILE_NAME))?;
+        ROOT_LOCK.get_or_init(|| file);
+        Ok(())
+    }
+
+    // A root is abandoned if its lock file can be locked, i.e., the process which locked it has exited.  A root without a
+    // lock file is still being created.
+    fn is_abandoned(root: &Path) -> bool {
+        match File::open(root.join(LOCK_FILE_NAME)) {
+            Ok(file) => file.try_lock().is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    fn default_capacity() -> usize {
+        env::var(TEST_THREADS_ENV_VAR)
+            .ok()
+            .and_then(|threads| threads.parse().ok())
+            .or_else(|| thread::available_parallelism().map(|n| n.get()).ok())
+            .unwrap_or(1)
+            .max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::database::TABLES;
+    use std::panic;
+
+    #[test]
+    fn test_env_is_reset_before_reuse() {
+        let env = {
+            let env_guard = EnvPoolGuard::new();
+            let mut conn = Conn::new(env_guard.env).unwrap();
+            let tx = conn.create_tx().unwrap();
+            for table in TABLES.iter().rev() {
+                tx.execute(&format!("DELETE FROM {};", table.name()), ()).unwrap();
+            }
+            drop(tx);
+            fs::write(Paths::new(env_guard.env).lookup(Dir::EnvOut).join("stale.txt"), "stale").unwrap();
+            env_guard.env
+        };
+
+        // Take environments until the one just returned is handed out again.
+        let mut guards = Vec::new();
+        loop {
+            let guard = EnvPoolGuard::new();
+            if guard.env == env {
+                let mut conn = Conn::new(guard.env).unwrap();
+                let mut tx = conn.create_test_tx().unwrap();
+                assert!(TABLES.iter().any(|t| t.count(&mut tx).unwrap() > 0));
+                assert!(!Paths::new(guard.env).lookup(Dir::EnvOut).join("stale.txt").exists());
+                break;
+            }
+            guards.push(guard);
+        }
+    }
+
+    #[test]
+    fn test_pool_survives_panicking_test() {
+        let result = panic::catch_unwind(|| {
+            let _env_guard = EnvPoolGuard::new();
+            panic!("test failure");
         });
+        assert!(result.is_err());
+        let env_guard = EnvPoolGuard::with_timeout(Duration::from_secs(60)).unwrap();
+        assert!(Env::is_test_env(env_guard.env));
+        assert!(env_guard.env != TEMPLATE_ENV);
     }
 
-    pub fn put(env: Env) {
-        let (lock, cvar) = &*LOCK_CV_PAIR;
-        let mut is_env_available = lock.lock().unwrap();
-        let mut pool = POOL.lock().unwrap();
-        pool.push(env);
-        *is_env_available = true;
-        cvar.notify_one();
+    #[test]
+    fn test_get_timeout_expires() {
+        // Take environments until the pool is exhausted, which must end with get_timeout giving up.
+        let mut guards = Vec::new();
+        loop {
+            let start = Instant::now();
+            match EnvPoolGuard::with_timeout(Duration::from_millis(20)) {
+                Some(guard) => guards.push(guard),
+                None => {
+                    assert!(start.elapsed() >= Duration::from_millis(20));
+                    break;
+                }
+            }
+        }
+        assert!(guards.len() <= EnvPool::capacity());
+    }
+
+    #[test]
+    fn test_is_abandoned_works() {
+        let env_guard = EnvPoolGuard::new();
+        let root = Paths::new(env_guard.env).lookup(Dir::EnvOut).join("test_is_abandoned_works");
+        fs::create_dir_all(&root).unwrap();
+        assert!(!EnvPool::is_abandoned(&root));
+
+        let file = File::create(root.join(LOCK_FILE_NAME)).unwrap();
+        file.try_lock().unwrap();
+        assert!(!EnvPool::is_abandoned(&root));
+        drop(file);
+        assert!(EnvPool::is_abandoned(&root));
+    }
+
+    #[test]
+    fn test_capacity_is_positive() {
+        assert!(EnvPool::capacity() >= 1);
     }
 }
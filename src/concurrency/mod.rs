@@ -2,5 +2,8 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 1/22/2026.
 
+#[cfg(feature = "async")]
+pub mod blocking_task;
+pub mod cancellation_token;
 #[cfg(test)]
 pub mod env_pool;
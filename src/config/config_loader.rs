@@ -0,0 +1,491 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::config::fgdb_config::{FgdbConfig, discover_config_path, read_toml};
+use crate::fs::data_root::data_root;
+use crate::types::game_variant::GameVariant;
+use crate::types::public_key::PublicKey;
+use crate::types::repository::Repository;
+use crate::util::consts;
+use crate::util::errors::FgdbError;
+use serde::Deserialize;
+use std::env;
+use std::error::Error as StdError;
+use std::ffi::OsString;
+use std::fmt;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use url::Url;
+
+/// The layer which supplied a configuration setting.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigSource {
+    // The built-in default.
+    Default,
+
+    // The configuration file at the given path.
+    File(PathBuf),
+
+    // The named environment variable.
+    Env(String),
+
+    // A programmatic override, e.g., a command line option.
+    Override,
+}
+
+impl Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::File(path) => write!(f, "file '{}'", path.display()),
+            ConfigSource::Env(name) => write!(f, "environment variable {}", name),
+            ConfigSource::Override => write!(f, "override"),
+        }
+    }
+}
+
+// The source of each setting in FgdbConfig.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigSources {
+    pub game_variant: ConfigSource,
+    pub repository: ConfigSource,
+    pub root_path: ConfigSource,
+    pub sound_path: ConfigSource,
+    pub repository_url: ConfigSource,
+    pub public_keys: ConfigSource,
+    pub connect_timeout_secs: ConfigSource,
+    pub timeout_secs: ConfigSource,
+}
+
+/// A configuration together with the source of each of its settings.  Displaying an EffectiveConfig produces
+/// fgdb.toml syntax in which each setting is followed by a comment naming its source.
+#[derive(Clone, Debug)]
+pub struct EffectiveConfig {
+    pub config: FgdbConfig,
+    pub sources: ConfigSources,
+}
+
+impl Default for EffectiveConfig {
+    fn default() -> Self {
+        Self {
+            config: FgdbConfig::local(data_root().join(consts::DEFAULT_REPOSITORY_DIR), GameVariant::default()),
+            sources: ConfigSources {
+                game_variant: ConfigSource::Default,
+                repository: ConfigSource::Default,
+                root_path: ConfigSource::Default,
+                sound_path: ConfigSource::Default,
+                repository_url: ConfigSource::Default,
+                public_keys: ConfigSource::Default,
+                connect_timeout_secs: ConfigSource::Default,
+                timeout_secs: ConfigSource::Default,
+            },
+        }
+    }
+}
+
+impl Display for EffectiveConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "game_variant = {}  # {}",
+            quote(&format!("{:?}", self.config.game_variant)),
+            self.sources.game_variant
+        )?;
+        writeln!(f, "repository = {}  # {}", quote(&self.config.repository.to_string()), self.sources.repository)?;
+        writeln!(
+            f,
+            "root_path = {}  # {}",
+            quote(&self.config.root_path.display().to_string()),
+            self.sources.root_path
+        )?;
+        match &self.config.sound_path {
+            Some(sound_path) => writeln!(f, "sound_path = {}  # {}", quote(&sound_path.display().to_string()), self.sources.sound_path),
+            None => writeln!(f, "# sound_path is not set  # {}", self.sources.sound_path),
+        }?;
+        match &self.config.repository_url {
+            Some(repository_url) => writeln!(f, "repository_url = {}  # {}", quote(repository_url.as_str()), self.sources.repository_url),
+            None => writeln!(f, "# repository_url is not set  # {}", self.sources.repository_url),
+        }?;
+        let public_keys = self
+            .config
+            .public_keys
+            .iter()
+            .map(|key| quote(&key.to_string()))
+            .collect::<Vec<String>>()
+            .join(", ");
+        writeln!(f, "public_keys = [{}]  # {}", public_keys, self.sources.public_keys)?;
+        writeln!(
+            f,
+            "connect_timeout_secs = {}  # {}",
+            self.config.connect_timeout().as_secs(),
+            self.sources.connect_timeout_secs
+        )?;
+        writeln!(f, "timeout_secs = {}  # {}", self.config.timeout().as_secs(), self.sources.timeout_secs)
+    }
+}
+
+impl EffectiveConfig {
+    /// Checks that the configured directories exist and that the repository is located.  root_path must exist when
+    /// the repository is local, repository_url must be set when the repository is remote, and sound_path, if set, must
+    /// exist.  Errors name the source of the offending setting.
+    pub fn validate(&self) -> Result<(), FgdbError> {
+        match self.config.repository {
+            Repository::Local => check_dir("root_path", &self.config.root_path, &self.sources.root_path)?,
+            Repository::Remote if self.config.repository_url.is_none() => {
+                return Err(FgdbError::Config(
+                    format!(
+                        "repository_url must be set when repository is \"Remote\" (repository set by {}).",
+                        self.sources.repository
+                    ),
+                    None,
+                ));
+            }
+            Repository::Remote => {}
+        }
+        if let Some(sound_path) = &self.config.sound_path {
+            check_dir("sound_path", sound_path, &self.sources.sound_path)?;
+        }
+        Ok(())
+    }
+
+    // Applies the settings present in layer, attributing each to the source returned for its name.
+    fn apply(&mut self, layer: ConfigLayer, source: impl Fn(&str) -> ConfigSource) {
+        if let Some(game_variant) = layer.game_variant {
+            self.config.game_variant = game_variant;
+            self.sources.game_variant = source("game_variant");
+        }
+        if let Some(repository) = layer.repository {
+            self.config.repository = repository;
+            self.sources.repository = source("repository");
+        }
+        if let Some(root_path) = layer.root_path {
+            self.config.root_path = root_path;
+            self.sources.root_path = source("root_path");
+        }
+        if let Some(sound_path) = layer.sound_path {
+            self.config.sound_path = Some(sound_path);
+            self.sources.sound_path = source("sound_path");
+        }
+        if let Some(repository_url) = layer.repository_url {
+            self.config.repository_url = Some(repository_url);
+            self.sources.repository_url = source("repository_url");
+        }
+        if let Some(public_keys) = layer.public_keys {
+            self.config.public_keys = public_keys;
+            self.sources.public_keys = source("public_keys");
+        }
+        if let Some(connect_timeout_secs) = layer.connect_timeout_secs {
+            self.config.connect_timeout_secs = Some(connect_timeout_secs);
+            self.sources.connect_timeout_secs = source("connect_timeout_secs");
+        }
+        if let Some(timeout_secs) = layer.timeout_secs {
+            self.config.timeout_secs = Some(timeout_secs);
+            self.sources.timeout_secs = source("timeout_secs");
+        }
+    }
+}
+
+// Settings supplied by a single layer.  Settings which are not present leave earlier layers unchanged.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub(crate) struct ConfigLayer {
+    game_variant: Option<GameVariant>,
+    repository: Option<Repository>,
+    root_path: Option<PathBuf>,
+    sound_path: Option<PathBuf>,
+    repository_url: Option<Url>,
+    public_keys: Option<Vec<PublicKey>>,
+    connect_timeout_secs: Option<u64>,
+    timeout_secs: Option<u64>,
+}
+
+/// Loads the configuration in layers.  Built-in defaults are overridden by the configuration file, which is overridden
+/// by FGDB_GAME_VARIANT, FGDB_REPOSITORY, FGDB_ROOT_PATH, FGDB_SOUND_PATH, FGDB_REPOSITORY_URL, FGDB_PUBLIC_KEYS,
+/// FGDB_CONNECT_TIMEOUT_SECS and FGDB_TIMEOUT_SECS, which are overridden by settings made using the loader, e.g., from
+/// command line options.  FGDB_PUBLIC_KEYS is a comma-separated list.
+#[derive(Clone, Debug, Default)]
+pub struct FgdbConfigLoader {
+    file: Option<PathBuf>,
+    overrides: ConfigLayer,
+}
+
+impl FgdbConfigLoader {
+    pub fn new() -> Self {
+        FgdbConfigLoader::default()
+    }
+
+    /// Reads the configuration file at path rather than the file found by find_config_path.  The file must exist.
+    pub fn file(mut self, path: impl Into<PathBuf>) -> FgdbConfigLoader {
+        self.file = Some(path.into());
+        self
+    }
+
+    pub fn game_variant(mut self, game_variant: GameVariant) -> FgdbConfigLoader {
+        self.overrides.game_variant = Some(game_variant);
+        self
+    }
+
+    pub fn repository(mut self, repository: Repository) -> FgdbConfigLoader {
+        self.overrides.repository = Some(repository);
+        self
+    }
+
+    pub fn root_path(mut self, root_path: impl Into<PathBuf>) -> FgdbConfigLoader {
+        self.overrides.root_path = Some(root_path.into());
+        self
+    }
+
+    pub fn sound_path(mut self, sound_path: impl Into<PathBuf>) -> FgdbConfigLoader {
+        self.overrides.sound_path = Some(sound_path.into());
+        self
+    }
+
+    pub fn repository_url(mut self, repository_url: Url) -> FgdbConfigLoader {
+        self.overrides.repository_url = Some(repository_url);
+        self
+    }
+
+    /// Sets the keys trusted to sign repository bundles, replacing any keys set by earlier layers.
+    pub fn public_keys(mut self, public_keys: impl IntoIterator<Item = PublicKey>) -> FgdbConfigLoader {
+        self.overrides.public_keys = Some(public_keys.into_iter().collect());
+        self
+    }
+
+    /// Sets the number of seconds allowed to connect to an HTTP server.
+    pub fn connect_timeout_secs(mut self, connect_timeout_secs: u64) -> FgdbConfigLoader {
+        self.overrides.connect_timeout_secs = Some(connect_timeout_secs);
+        self
+    }
+
+    /// Sets the number of seconds allowed for a whole HTTP request, including the download of the bundle.
+    pub fn timeout_secs(mut self, timeout_secs: u64) -> FgdbConfigLoader {
+        self.overrides.timeout_secs = Some(timeout_secs);
+        self
+    }
+
+    /// Loads and validates the configuration.
+    pub fn load(&self) -> Result<EffectiveConfig, FgdbError> {
+        let config = self.load_unvalidated()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Loads the configuration without checking that its directories exist.
+    pub fn load_unvalidated(&self) -> Result<EffectiveConfig, FgdbError> {
+        self.load_with(|name| env::var_os(name))
+    }
+
+    // Loads the configuration, reading environment variables using var.
+    fn load_with(&self, var: impl Fn(&str) -> Option<OsString>) -> Result<EffectiveConfig, FgdbError> {
+        let mut config = EffectiveConfig::default();
+
+        let file = match &self.file {
+            Some(file) => Some(file.clone()),
+            None => discover_config_path(var(consts::CONFIG_ENV_VAR)),
+        };
+        if let Some(file) = file {
+            let layer: ConfigLayer = read_toml(&file)?;
+            config.apply(layer, |_| ConfigSource::File(file.clone()));
+        }
+
+        let layer = ConfigLayer {
+            game_variant: env_value(&var, consts::GAME_VARIANT_ENV_VAR)?,
+            repository: env_value(&var, consts::REPOSITORY_ENV_VAR)?,
+            root_path: env_value(&var, consts::ROOT_PATH_ENV_VAR)?,
+            sound_path: env_value(&var, consts::SOUND_PATH_ENV_VAR)?,
+            repository_url: env_value(&var, consts::REPOSITORY_URL_ENV_VAR)?,
+            public_keys: env_list(&var, consts::PUBLIC_KEYS_ENV_VAR)?,
+            connect_timeout_secs: env_value(&var, consts::CONNECT_TIMEOUT_SECS_ENV_VAR)?,
+            timeout_secs: env_value(&var, consts::TIMEOUT_SECS_ENV_VAR)?,
+        };
+        config.apply(layer, |name| {
+            ConfigSource::Env(
+                match name {
+                    "game_variant" => consts::GAME_VARIANT_ENV_VAR,
+                    "repository" => consts::REPOSITORY_ENV_VAR,
+                    "root_path" => consts::ROOT_PATH_ENV_VAR,
+                    "sound_path" => consts::SOUND_PATH_ENV_VAR,
+                    "repository_url" => consts::REPOSITORY_URL_ENV_VAR,
+                    "public_keys" => consts::PUBLIC_KEYS_ENV_VAR,
+                    "connect_timeout_secs" => consts::CONNECT_TIMEOUT_SECS_ENV_VAR,
+                    _ => consts::TIMEOUT_SECS_ENV_VAR,
+                }
+                .to_string(),
+            )
+        });
+
+        config.apply(self.overrides.clone(), |_| ConfigSource::Override);
+        Ok(config)
+    }
+}
+
+fn check_dir(name: &str, path: &Path, source: &ConfigSource) -> Result<(), FgdbError> {
+    if path.is_dir() {
+        return Ok(());
+    }
+    Err(FgdbError::Config(
+        format!(
+            "{} '{}' (set by {}) is not an existing directory.  Create the directory or set {} to an existing directory.",
+            name,
+            path.display(),
+            source,
+            name
+        ),
+        None,
+    ))
+}
+
+// Parses the environment variable name, returning None if it is not set.
+fn env_value<T>(var: impl Fn(&str) -> Option<OsString>, name: &str) -> Result<Option<T>, FgdbError>
+where
+    T: FromStr,
+    T::Err: StdError + Send + Sync + 'static,
+{
+    let Some(value) = var(name) else {
+        return Ok(None);
+    };
+    let value = value.to_string_lossy();
+    value.parse().map(Some).map_err(|e| invalid_env_value(name, &value, e))
+}
+
+// Parses the environment variable name as a comma-separated list, returning None if it is not set.  Empty items are
+// ignored.
+fn env_list<T>(var: impl Fn(&str) -> Option<OsString>, name: &str) -> Result<Option<Vec<T>>, FgdbError>
+where
+    T: FromStr,
+    T::Err: StdError + Send + Sync + 'static,
+{
+    let Some(value) = var(name) else {
+        return Ok(None);
+    };
+    let value = value.to_string_lossy();
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| item.parse().map_err(|e| invalid_env_value(name, &value, e)))
+        .collect::<Result<Vec<T>, FgdbError>>()
+        .map(Some)
+}
+
+fn invalid_env_value(name: &str, value: &str, e: impl StdError + Send + Sync + 'static) -> FgdbError {
+    FgdbError::Config(format!("Environment variable {} has invalid value '{}': {}", name, value, e), Some(Box::new(e)))
+}
+
+fn quote(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::time::Duration;
+
+    fn vars(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<OsString> {
+        let vars: HashMap<String, OsString> = vars.iter().map(|(k, v)| (k.to_string(), OsString::from(v))).collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_layers_override_in_order() {
+        let env_guard = EnvPoolGuard::new();
+        let dir = env_guard.out_dir("test_layers_override_in_order");
+        let file = dir.join(consts::CONFIG_TOML);
+        fs::write(&file, "game_variant = \"Poe2\"\nrepository = \"Remote\"\nroot_path = \"file\"\n").unwrap();
+
+        let loader = FgdbConfigLoader::new().file(&file).root_path("override");
+        let config = loader
+            .load_with(vars(&[(consts::REPOSITORY_ENV_VAR, "Local"), (consts::ROOT_PATH_ENV_VAR, "env")]))
+            .unwrap();
+
+        assert_eq!(GameVariant::Poe2, config.config.game_variant);
+        assert_eq!(ConfigSource::File(file.clone()), config.sources.game_variant);
+        assert_eq!(Repository::Local, config.config.repository);
+        assert_eq!(ConfigSource::Env(consts::REPOSITORY_ENV_VAR.to_string()), config.sources.repository);
+        assert_eq!(PathBuf::from("override"), config.config.root_path);
+        assert_eq!(ConfigSource::Override, config.sources.root_path);
+        assert_eq!(None, config.config.sound_path);
+        assert_eq!(ConfigSource::Default, config.sources.sound_path);
+
+        let dump = config.to_string();
+        assert!(dump.contains("game_variant = \"Poe2\"  # file '"));
+        assert!(dump.contains("repository = \"Local\"  # environment variable FGDB_REPOSITORY"));
+        assert!(dump.contains("root_path = \"override\"  # override"));
+        assert!(dump.contains("# sound_path is not set  # default"));
+    }
+
+    #[test]
+    fn test_invalid_environment_variable_is_reported() {
+        let result = FgdbConfigLoader::new()
+            .file(consts::CONFIG_TOML)
+            .load_with(vars(&[(consts::GAME_VARIANT_ENV_VAR, "Poe3")]));
+        assert!(matches!(result, Err(FgdbError::Config(message, _)) if message.contains("FGDB_GAME_VARIANT has invalid value 'Poe3'")));
+    }
+
+    #[test]
+    fn test_public_keys_are_loaded() {
+        let env_guard = EnvPoolGuard::new();
+        let dir = env_guard.out_dir("test_public_keys_are_loaded");
+        let (old, new) = (PublicKey::from_bytes([1; 32]), PublicKey::from_bytes([2; 32]));
+        let file = dir.join(consts::CONFIG_TOML);
+        fs::write(&file, format!("public_keys = [\"{}\"]\n", old)).unwrap();
+
+        let config = FgdbConfigLoader::new().file(&file).load_with(vars(&[])).unwrap();
+        assert_eq!(vec![old], config.config.public_keys);
+        assert!(config.to_string().contains(&format!("public_keys = [\"{}\"]  # file '", old)));
+
+        let keys = format!("{}, {}", old, new);
+        let config = FgdbConfigLoader::new()
+            .file(&file)
+            .load_with(vars(&[(consts::PUBLIC_KEYS_ENV_VAR, &keys)]))
+            .unwrap();
+        assert_eq!(vec![old, new], config.config.public_keys);
+        assert_eq!(ConfigSource::Env(consts::PUBLIC_KEYS_ENV_VAR.to_string()), config.sources.public_keys);
+
+        let result = FgdbConfigLoader::new().file(&file).load_with(vars(&[(consts::PUBLIC_KEYS_ENV_VAR, "AAAA")]));
+        assert!(matches!(result, Err(FgdbError::Config(message, _)) if message.contains("FGDB_PUBLIC_KEYS has invalid value 'AAAA'")));
+    }
+
+    #[test]
+    fn test_timeouts_are_loaded() {
+        let config = FgdbConfigLoader::new().file(consts::CONFIG_TOML).load_with(vars(&[])).unwrap();
+        assert_eq!(Duration::from_secs(consts::DEFAULT_CONNECT_TIMEOUT_SECS), config.config.connect_timeout());
+        assert_eq!(Duration::from_secs(consts::DEFAULT_TIMEOUT_SECS), config.config.timeout());
+
+        let loader = FgdbConfigLoader::new().file(consts::CONFIG_TOML).timeout_secs(60);
+        let config = loader
+            .load_with(vars(&[(consts::CONNECT_TIMEOUT_SECS_ENV_VAR, "5"), (consts::TIMEOUT_SECS_ENV_VAR, "30")]))
+            .unwrap();
+        assert_eq!(Duration::from_secs(5), config.config.connect_timeout());
+        assert_eq!(Duration::from_secs(60), config.config.timeout());
+        assert!(
+            config
+                .to_string()
+                .contains("connect_timeout_secs = 5  # environment variable FGDB_CONNECT_TIMEOUT_SECS")
+        );
+        assert!(config.to_string().contains("timeout_secs = 60  # override"));
+    }
+
+    #[test]
+    fn test_validate_requires_local_root_path() {
+        let mut config = FgdbConfigLoader::new()
+            .repository(Repository::Local)
+            .root_path("missing")
+            .load_with(vars(&[]))
+            .unwrap();
+        let result = config.validate();
+        assert!(matches!(result, Err(FgdbError::Config(message, _)) if message.contains("root_path 'missing' (set by override)")));
+
+        config.config.repository = Repository::Remote;
+        assert!(matches!(config.validate(), Err(FgdbError::Config(message, _)) if message.contains("repository_url must be set")));
+        config.config.repository_url = Some(Url::parse("https://example.com/fgdb").unwrap());
+        config.validate().unwrap();
+
+        config.config.sound_path = Some(PathBuf::from("missing"));
+        assert!(config.validate().is_err());
+    }
+}
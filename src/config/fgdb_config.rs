@@ -2,14 +2,23 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 1/16/2026.
 
+use crate::config::config_loader::FgdbConfigLoader;
+use crate::fs::data_root::data_root;
 use crate::types::game_variant::GameVariant;
+use crate::types::public_key::PublicKey;
 use crate::types::repository::Repository;
 use crate::util::consts;
-use anyhow::{Error, Result};
+use crate::util::errors::FgdbError;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use std::env;
+use std::ffi::OsString;
 use std::fs;
-use std::path::PathBuf;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
+use std::time::Duration;
+use url::Url;
 
 static FGDB_CONFIG: OnceLock<FgdbConfig> = OnceLock::new();
 
@@ -20,17 +29,142 @@ pub struct FgdbConfig {
     pub repository: Repository,
 
     pub root_path: PathBuf,
+
+    #[serde(default)]
+    pub sound_path: Option<PathBuf>,
+
+    #[serde(default)]
+    pub repository_url: Option<Url>,
+
+    #[serde(default)]
+    pub public_keys: Vec<PublicKey>,
+
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
 }
 
 impl FgdbConfig {
-    pub fn new() -> Result<FgdbConfig, Error> {
-        let contents = fs::read_to_string(consts::CONFIG_TOML)?;
-        Ok(toml::from_str(&contents)?)
+    /// Loads the configuration from defaults, the configuration file found by find_config_path and FGDB_*
+    /// environment variables.  The configuration is not validated; use FgdbConfig::loader to validate it or to apply
+    /// overrides.
+    pub fn new() -> Result<FgdbConfig, FgdbError> {
+        Ok(FgdbConfig::loader().load_unvalidated()?.config)
+    }
+
+    /// Creates a configuration for the local repository at root_path holding data for game_variant.  All other settings
+    /// are unset.
+    pub fn local(root_path: PathBuf, game_variant: GameVariant) -> FgdbConfig {
+        FgdbConfig {
+            game_variant,
+            repository: Repository::Local,
+            root_path,
+            sound_path: None,
+            repository_url: None,
+            public_keys: Vec::new(),
+            connect_timeout_secs: None,
+            timeout_secs: None,
+        }
+    }
+
+    /// Returns a loader for a layered configuration.
+    pub fn loader() -> FgdbConfigLoader {
+        FgdbConfigLoader::new()
+    }
+
+    /// Loads the configuration as FgdbConfig::new does but from the configuration file at path, which must exist.
+    /// Settings the file omits keep their defaults.  Errors name the file and describe how to correct it.
+    pub fn from_path(path: &Path) -> Result<FgdbConfig, FgdbError> {
+        Ok(FgdbConfig::loader().file(path).load_unvalidated()?.config)
+    }
+
+    /// Returns the time allowed to connect to an HTTP server.
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_secs(self.connect_timeout_secs.unwrap_or(consts::DEFAULT_CONNECT_TIMEOUT_SECS))
+    }
+
+    /// Returns the time allowed for a whole HTTP request, including reading the response body.
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs.unwrap_or(consts::DEFAULT_TIMEOUT_SECS))
+    }
+}
+
+/// Finds the configuration file.  The file named by the FGDB_CONFIG environment variable is used if set; otherwise
+/// fgdb.toml is searched for in the current directory, the data root and the fgdb directory within the platform config
+/// directory, e.g., %APPDATA%\fgdb on Windows or ~/.config/fgdb on Linux.
+pub fn find_config_path() -> Result<PathBuf, FgdbError> {
+    discover_config_path(env::var_os(consts::CONFIG_ENV_VAR)).ok_or_else(|| {
+        FgdbError::Config(
+            format!(
+                "Configuration file {} not found.  Searched {}.  Create it in one of these locations or set {} to its path.",
+                consts::CONFIG_TOML,
+                config_candidates().iter().map(|c| format!("'{}'", c.display())).collect::<Vec<_>>().join(", "),
+                consts::CONFIG_ENV_VAR
+            ),
+            None,
+        )
+    })
+}
+
+// Returns config_env_var, the value of FGDB_CONFIG, if set; otherwise the first configuration file candidate which
+// exists.
+pub(crate) fn discover_config_path(config_env_var: Option<OsString>) -> Option<PathBuf> {
+    match config_env_var {
+        Some(path) => Some(PathBuf::from(path)),
+        None => config_candidates().into_iter().find(|c| c.is_file()),
+    }
+}
+
+fn config_candidates() -> Vec<PathBuf> {
+    let mut candidates = vec![PathBuf::from(consts::CONFIG_TOML), data_root().join(consts::CONFIG_TOML)];
+    if let Some(config_dir) = dirs::config_dir() {
+        candidates.push(config_dir.join(consts::APP_DIR_NAME).join(consts::CONFIG_TOML));
+    }
+    candidates
+}
+
+// Reads the TOML file at path.  Errors name the file and describe how to correct it.
+pub(crate) fn read_toml<T: DeserializeOwned>(path: &Path) -> Result<T, FgdbError> {
+    let contents = fs::read_to_string(path).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => FgdbError::Config(
+            format!(
+                "Configuration file '{}' not found.  Create it or name an existing file, e.g., using {}; every setting in the file is optional.",
+                path.display(),
+                consts::CONFIG_ENV_VAR
+            ),
+            Some(Box::new(e)),
+        ),
+        _ => FgdbError::Config(format!("Configuration file '{}' could not be read: {}.", path.display(), e), Some(Box::new(e))),
+    })?;
+    toml::from_str(&contents).map_err(|e| {
+        FgdbError::Config(
+            format!("Configuration file '{}' is malformed: {}", path.display(), e.message()),
+            Some(Box::new(e)),
+        )
+    })
+}
+
+/// Returns the configuration, loading it on first use.  If fgdb.toml is malformed or an FGDB_* environment variable
+/// is invalid, an error describing the problem is returned and the next call tries again.
+pub fn try_get_config() -> Result<&'static FgdbConfig, FgdbError> {
+    if let Some(config) = FGDB_CONFIG.get() {
+        return Ok(config);
     }
+    let config = FgdbConfig::new()?;
+    Ok(FGDB_CONFIG.get_or_init(|| config))
 }
 
+/// Returns the configuration, loading it on first use.
+///
+/// # Panics
+///
+/// Panics if fgdb.toml is malformed or an FGDB_* environment variable is invalid.  Applications should call
+/// try_get_config, or Initializer::try_new, at startup so that configuration problems are reported rather than
+/// panicking later.
 pub fn get_config() -> &'static FgdbConfig {
-    FGDB_CONFIG.get_or_init(|| FgdbConfig::new().unwrap())
+    try_get_config().unwrap_or_else(|e| panic!("{}", e))
 }
 
 #[cfg(test)]
@@ -45,5 +179,29 @@ mod tests {
     #[test]
     fn test_get_config_works() {
         let _config = get_config();
+        let _config = try_get_config().unwrap();
+    }
+
+    #[test]
+    fn test_find_config_path_works() {
+        assert!(find_config_path().unwrap().ends_with(consts::CONFIG_TOML));
+    }
+
+    #[test]
+    fn test_from_path_reports_missing_and_malformed_files() {
+        let result = FgdbConfig::from_path(Path::new("missing.toml"));
+        assert!(matches!(result, Err(FgdbError::Config(message, _)) if message.contains("'missing.toml' not found")));
+
+        let path = std::env::temp_dir().join("fgdb_test_from_path_reports_missing_and_malformed_files.toml");
+        fs::write(&path, "game_variant = \"Poe3\"").unwrap();
+        let result = FgdbConfig::from_path(&path);
+        assert!(matches!(result, Err(FgdbError::Config(message, _)) if message.contains("is malformed")));
+
+        // Settings which the file omits keep their defaults.
+        fs::write(&path, "repository = \"Local\"").unwrap();
+        let config = FgdbConfig::from_path(&path).unwrap();
+        assert_eq!(data_root().join(consts::DEFAULT_REPOSITORY_DIR), config.root_path);
+        assert_eq!(Vec::<PublicKey>::new(), config.public_keys);
+        fs::remove_file(&path).unwrap();
     }
 }
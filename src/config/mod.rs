@@ -2,4 +2,5 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 1/16/2026.
 
+pub mod config_loader;
 pub mod fgdb_config;
@@ -2,15 +2,18 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 1/19/2026.
 
-use crate::config::fgdb_config::get_config;
+use crate::config::fgdb_config::try_get_config;
+use crate::db::database::Database;
 use crate::db::tx::Tx;
 use crate::fs::dir::Dir;
 use crate::fs::paths::Paths;
 use crate::types::game_variant::GameVariant;
 use crate::util::consts;
 use crate::util::env::Env;
-use anyhow::{Error, Result};
-use rusqlite::{Connection, DropBehavior};
+use crate::util::errors::FgdbError;
+use rusqlite::{Connection, DropBehavior, OpenFlags};
+use std::path::Path;
+use std::time::Duration;
 
 pub struct Conn {
     pub conn: Connection,
@@ -19,20 +22,88 @@ pub struct Conn {
 }
 
 impl Conn {
-    pub fn new(env: Env) -> Result<Self, Error> {
-        Conn::create(get_config().game_variant, env)
+    pub fn new(env: Env) -> Result<Self, FgdbError> {
+        Conn::create(try_get_config()?.game_variant, env)
     }
 
-    pub fn create(game_variant: GameVariant, env: Env) -> Result<Self, Error> {
-        let paths = Paths::create(game_variant, env);
+    pub fn create(game_variant: GameVariant, env: Env) -> Result<Self, FgdbError> {
+        Conn::with_paths(&Paths::create(game_variant, env))
+    }
+
+    /// Opens the database for the game variant and environment of paths, resolving its location through paths rather
+    /// than the global configuration.  The database is switched to WAL journalling so that readers are not blocked by
+    /// a writer, and the connection waits up to the default busy timeout for locks held by other connections.
+    pub fn with_paths(paths: &Paths) -> Result<Self, FgdbError> {
+        let conn = Connection::open(paths.lookup(Dir::EnvDb).join(consts::DB_NAME))?;
+        conn.busy_timeout(Duration::from_millis(consts::DB_BUSY_TIMEOUT_MS))?;
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<usize, String>(0))?;
+        Ok(Self {
+            conn,
+            env: paths.env(),
+            game_variant: paths.game_variant(),
+        })
+    }
+
+    /// Opens the database located by paths for reading only.  Attempts to modify the database fail.
+    pub fn read_only(paths: &Paths) -> Result<Self, FgdbError> {
+        let flags = OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+        let conn = Connection::open_with_flags(paths.lookup(Dir::EnvDb).join(consts::DB_NAME), flags)?;
+        conn.busy_timeout(Duration::from_millis(consts::DB_BUSY_TIMEOUT_MS))?;
+        Ok(Self {
+            conn,
+            env: paths.env(),
+            game_variant: paths.game_variant(),
+        })
+    }
+
+    /// Opens a private, empty in-memory database.  The database is discarded when the connection is closed.  Output
+    /// paths for the connection resolve to the production environment.
+    pub fn in_memory(game_variant: GameVariant) -> Result<Self, FgdbError> {
+        Ok(Self {
+            conn: Connection::open_in_memory()?,
+            env: Env::Prod,
+            game_variant,
+        })
+    }
+
+    /// Opens the in-memory database called name using SQLite's shared cache, creating it if necessary.  Connections
+    /// opened with the same name within a process share one database, which is discarded when the last of them is
+    /// closed.  Output paths for the connection resolve to the production environment.
+    pub fn shared_in_memory(name: &str, game_variant: GameVariant) -> Result<Self, FgdbError> {
+        let uri = format!("file:{}?mode=memory&cache=shared", name);
+        let flags = OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE | OpenFlags::SQLITE_OPEN_URI;
         Ok(Self {
-            conn: Connection::open(paths.lookup(Dir::EnvDb).join(consts::DB_NAME))?,
-            env,
+            conn: Connection::open_with_flags(uri, flags)?,
+            env: Env::Prod,
             game_variant,
         })
     }
 
-    pub fn create_test_tx(&mut self) -> Result<Tx, Error> {
+    /// Creates any missing tables and imports every table from the JSON files in dir.  Nothing is imported unless
+    /// every table imports successfully.
+    pub fn populate(&mut self, dir: &Path) -> Result<(), FgdbError> {
+        let db = Database::new()?;
+        let mut tx = self.create_rollback_tx()?;
+        db.create(&mut tx)?;
+        db.import_dir(dir, &mut tx)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Populates the database with the test data for the connection's game variant.
+    pub fn populate_test_data(&mut self) -> Result<(), FgdbError> {
+        self.populate(&Paths::create(self.game_variant, Env::Prod).lookup(Dir::AssetsJsonTest))
+    }
+
+    /// Begins a transaction for a test.  The transaction is rolled back when dropped, so tests leave the database
+    /// unchanged.  Production code uses create_tx or create_rollback_tx.
+    pub fn create_test_tx(&mut self) -> Result<Tx, FgdbError> {
+        self.create_rollback_tx()
+    }
+
+    /// Begins a transaction which is rolled back when dropped unless it is committed with Tx::commit.  Use it for
+    /// changes which must be made in full or not at all, and for reads which must see a single snapshot.
+    pub fn create_rollback_tx(&mut self) -> Result<Tx<'_>, FgdbError> {
         // Note: The default Rusqlite transaction drop mode is rollback.
         Ok(Tx {
             tx: self.conn.transaction()?,
@@ -41,7 +112,7 @@ impl Conn {
         })
     }
 
-    pub fn create_tx(&mut self) -> Result<Tx, Error> {
+    pub fn create_tx(&mut self) -> Result<Tx, FgdbError> {
         let mut tx = self.conn.transaction()?;
         tx.set_drop_behavior(DropBehavior::Commit);
         Ok(Tx {
@@ -51,3 +122,46 @@ impl Conn {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use crate::db::database::TABLES;
+    use rusqlite::params;
+
+    #[test]
+    fn test_populate_test_data_matches_test_env() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let expected = TABLES.iter().map(|t| t.count(&mut tx).unwrap()).collect::<Vec<_>>();
+
+        let mut memory = Conn::in_memory(try_get_config().unwrap().game_variant).unwrap();
+        memory.populate_test_data().unwrap();
+        let mut tx = memory.create_test_tx().unwrap();
+        assert_eq!(expected, TABLES.iter().map(|t| t.count(&mut tx).unwrap()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_shared_in_memory_databases_are_shared_by_name() {
+        let first = Conn::shared_in_memory("test_shared_in_memory_databases_are_shared_by_name", GameVariant::Poe1).unwrap();
+        first.conn.execute("CREATE TABLE shared (value INTEGER NOT NULL) STRICT", ()).unwrap();
+        first.conn.execute("INSERT INTO shared (value) VALUES (?1)", params![42]).unwrap();
+
+        let second = Conn::shared_in_memory("test_shared_in_memory_databases_are_shared_by_name", GameVariant::Poe1).unwrap();
+        let value: i64 = second.conn.query_row("SELECT value FROM shared", (), |row| row.get(0)).unwrap();
+        assert_eq!(42, value);
+
+        let private = Conn::in_memory(GameVariant::Poe1).unwrap();
+        assert!(private.conn.query_row("SELECT value FROM shared", (), |row| row.get::<usize, i64>(0)).is_err());
+    }
+
+    #[test]
+    fn test_populate_rolls_back_on_failure() {
+        let mut conn = Conn::in_memory(GameVariant::Poe1).unwrap();
+        assert!(conn.populate(Path::new("missing")).is_err());
+        let count: i64 = conn.conn.query_row("SELECT COUNT(*) FROM sqlite_schema", (), |row| row.get(0)).unwrap();
+        assert_eq!(0, count);
+    }
+}
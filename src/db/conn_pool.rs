@@ -0,0 +1,208 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::db::conn::Conn;
+use crate::fs::paths::Paths;
+use crate::util::consts;
+use crate::util::errors::FgdbError;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard, PoisonError, TryLockError};
+use std::time::Duration;
+
+/// A pool of connections to one database.  Reads use read-only connections which are reused once returned.  Writes use
+/// a single writer connection guarded by the writer lock, so writes within the process are serialized.  The database
+/// uses WAL journalling, so readers continue to see the last committed state while a write, e.g., an update, is in
+/// progress, and every connection waits up to the busy timeout for locks held elsewhere.
+pub struct ConnPool {
+    paths: Paths,
+    busy_timeout: Duration,
+    max_idle_readers: usize,
+    readers: Mutex<Vec<Conn>>,
+    writer: Mutex<Conn>,
+}
+
+impl ConnPool {
+    pub fn builder(paths: Paths) -> ConnPoolBuilder {
+        ConnPoolBuilder {
+            paths,
+            busy_timeout: Duration::from_millis(consts::DB_BUSY_TIMEOUT_MS),
+            max_idle_readers: consts::DB_MAX_IDLE_READERS,
+        }
+    }
+
+    /// Creates a pool for the database located by paths using the default busy timeout and number of idle readers.
+    pub fn with_paths(paths: Paths) -> Result<Self, FgdbError> {
+        ConnPool::builder(paths).build()
+    }
+
+    pub fn busy_timeout(&self) -> Duration {
+        self.busy_timeout
+    }
+
+    pub fn max_idle_readers(&self) -> usize {
+        self.max_idle_readers
+    }
+
+    pub fn paths(&self) -> &Paths {
+        &self.paths
+    }
+
+    /// Returns a read-only connection, reusing an idle connection if one is available.
+    pub fn read(&self) -> Result<ReadConn<'_>, FgdbError> {
+        let idle = self.lock_readers().pop();
+        let conn = match idle {
+            Some(conn) => conn,
+            None => {
+                let conn = Conn::read_only(&self.paths)?;
+                conn.conn.busy_timeout(self.busy_timeout)?;
+                conn
+            }
+        };
+        Ok(ReadConn { pool: self, inner: Some(conn) })
+    }
+
+    /// Returns the writer connection, waiting until no other writer in the process holds it.
+    pub fn write(&self) -> MutexGuard<'_, Conn> {
+        // A writer which panicked left no transaction open since Tx rolls back or commits on drop.
+        self.writer.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the writer connection if no other writer in the process holds it.
+    pub fn try_write(&self) -> Option<MutexGuard<'_, Conn>> {
+        match self.writer.try_lock() {
+            Ok(writer) => Some(writer),
+            Err(TryLockError::Poisoned(e)) => Some(e.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+
+    fn lock_readers(&self) -> MutexGuard<'_, Vec<Conn>> {
+        self.readers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// Builder for ConnPool.
+#[derive(Debug)]
+pub struct ConnPoolBuilder {
+    paths: Paths,
+
+    busy_timeout: Duration,
+
+    max_idle_readers: usize,
+}
+
+impl ConnPoolBuilder {
+    /// Opens the writer connection, switching the database to WAL journalling.
+    pub fn build(self) -> Result<ConnPool, FgdbError> {
+        let writer = Conn::with_paths(&self.paths)?;
+        writer.conn.busy_timeout(self.busy_timeout)?;
+        Ok(ConnPool {
+            paths: self.paths,
+            busy_timeout: self.busy_timeout,
+            max_idle_readers: self.max_idle_readers,
+            readers: Mutex::new(Vec::new()),
+            writer: Mutex::new(writer),
+        })
+    }
+
+    pub fn busy_timeout(mut self, busy_timeout: Duration) -> ConnPoolBuilder {
+        self.busy_timeout = busy_timeout;
+        self
+    }
+
+    pub fn max_idle_readers(mut self, max_idle_readers: usize) -> ConnPoolBuilder {
+        self.max_idle_readers = max_idle_readers;
+        self
+    }
+}
+
+/// A read-only connection borrowed from a ConnPool.  The connection is returned to the pool when dropped.
+pub struct ReadConn<'a> {
+    pool: &'a ConnPool,
+    inner: Option<Conn>,
+}
+
+impl Deref for ReadConn<'_> {
+    type Target = Conn;
+    fn deref(&self) -> &Self::Target {
+        self.inner.as_ref().expect("connection is present until dropped")
+    }
+}
+
+impl DerefMut for ReadConn<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.inner.as_mut().expect("connection is present until dropped")
+    }
+}
+
+impl Drop for ReadConn<'_> {
+    fn drop(&mut self) {
+        if let Some(conn) = self.inner.take() {
+            let mut readers = self.pool.lock_readers();
+            if readers.len() < self.pool.max_idle_readers {
+                readers.push(conn);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use crate::db::tables::classes_table::ClassesTable;
+    use crate::db::tables::table::{GenericTable, Table};
+    use rusqlite::params;
+
+    #[test]
+    fn test_readers_see_committed_state_during_write() {
+        let env_guard = EnvPoolGuard::new();
+        let pool = ConnPool::with_paths(Paths::new(env_guard.env)).unwrap();
+        let table = ClassesTable::new();
+        let count = table.count(&mut pool.read().unwrap().create_tx().unwrap()).unwrap();
+
+        let mut writer = pool.write();
+        let tx = writer.create_tx().unwrap();
+        tx.execute("INSERT INTO classes (class) VALUES (?1);", params!["test_readers_see_committed_state_during_write"]).unwrap();
+        assert_eq!(count, table.count(&mut pool.read().unwrap().create_tx().unwrap()).unwrap());
+        drop(tx);
+        drop(writer);
+
+        assert_eq!(count + 1, table.count(&mut pool.read().unwrap().create_tx().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_pool_uses_wal_and_read_only_readers() {
+        let env_guard = EnvPoolGuard::new();
+        let pool = ConnPool::builder(Paths::new(env_guard.env)).busy_timeout(Duration::from_millis(100)).build().unwrap();
+        let journal_mode: String = pool.write().conn.query_row("PRAGMA journal_mode;", (), |row| row.get(0)).unwrap();
+        assert_eq!("wal", journal_mode);
+
+        let reader = pool.read().unwrap();
+        assert!(reader.conn.execute("DELETE FROM classes;", ()).is_err());
+    }
+
+    #[test]
+    fn test_idle_readers_are_reused_up_to_limit() {
+        let env_guard = EnvPoolGuard::new();
+        let pool = ConnPool::builder(Paths::new(env_guard.env)).max_idle_readers(1).build().unwrap();
+        let first = pool.read().unwrap();
+        let second = pool.read().unwrap();
+        drop(first);
+        drop(second);
+        assert_eq!(1, pool.lock_readers().len());
+        let _reader = pool.read().unwrap();
+        assert_eq!(0, pool.lock_readers().len());
+    }
+
+    #[test]
+    fn test_writer_lock_serializes_writers() {
+        let env_guard = EnvPoolGuard::new();
+        let pool = ConnPool::with_paths(Paths::new(env_guard.env)).unwrap();
+        let writer = pool.write();
+        assert!(pool.try_write().is_none());
+        drop(writer);
+        assert!(pool.try_write().is_some());
+    }
+}
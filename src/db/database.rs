@@ -2,6 +2,7 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 1/21/2026.
 
+use crate::concurrency::cancellation_token::CancellationToken;
 use crate::db::tables::action_sets_table::ActionSetsTable;
 use crate::db::tables::armor_types_table::ArmorTypesTable;
 use crate::db::tables::async_prices_table::AsyncPricesTable;
@@ -16,11 +17,17 @@ use crate::db::tables::table::Table;
 use crate::db::tx::Tx;
 use crate::fs::dir::Dir;
 use crate::fs::paths::Paths;
+use crate::repo::manifest::Manifest;
+use crate::types::format_version::FormatVersion;
+use crate::update::price_refresher::create_refresh_table;
+use crate::update::progress::{NoProgress, Progress, ProgressObserver};
 use crate::util::env::Env;
-use anyhow::{Error, Result};
+use crate::util::errors::FgdbError;
 use static_init::dynamic;
+use std::fs;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
 
 // N.B.: The order of tables in this array is important.  It must be possible to create each table
 // in order of appearance and to drop/delete each table in reverse order of appearance.
@@ -41,35 +48,79 @@ pub static TABLES: [Box<dyn Table>; 10] = [
 pub struct Database;
 
 impl Database {
-    pub fn new() -> Result<Self, Error> {
+    pub fn new() -> Result<Self, FgdbError> {
         Ok(Self {})
     }
 
-    pub fn create(&self, tx: &mut Tx) -> Result<(), Error> {
+    pub fn create(&self, tx: &mut Tx) -> Result<(), FgdbError> {
         for table in TABLES.iter() {
             table.create(tx)?;
         }
-        Ok(())
+        create_refresh_table(tx)
     }
 
-    pub fn delete(&self, tx: &mut Tx) -> Result<(), Error> {
+    pub fn delete(&self, tx: &mut Tx) -> Result<(), FgdbError> {
         for table in TABLES.iter().rev() {
             table.delete(tx)?;
         }
         Ok(())
     }
 
-    pub fn drop_tables(&self, tx: &mut Tx) -> Result<(), Error> {
+    pub fn drop_tables(&self, tx: &mut Tx) -> Result<(), FgdbError> {
         for table in TABLES.iter().rev() {
             table.drop_table(tx)?;
         }
         Ok(())
     }
 
-    pub fn import(&self, tx: &mut Tx) -> Result<(), Error> {
+    /// Exports every table to dir in the given format together with a manifest recording the format.
+    pub fn export(&self, dir: &Path, format_version: FormatVersion, tx: &mut Tx) -> Result<(), FgdbError> {
+        fs::create_dir_all(dir)?;
+        for table in TABLES.iter() {
+            let dst_path = dir.join(format!("{}.json", table.name()));
+            let mut writer = BufWriter::new(File::create(&dst_path)?);
+            table.export(&mut writer, format_version, tx)?;
+            writer.flush()?;
+        }
+        Manifest::new(format_version).write(dir)
+    }
+
+    pub fn import(&self, tx: &mut Tx) -> Result<(), FgdbError> {
+        let paths = Paths::create(tx.game_variant, Env::Prod);
+        self.import_dir(&paths.lookup(Dir::CacheJson), tx)
+    }
+
+    /// Imports every table from the JSON files in dir.  Reading the manifest rejects formats newer than this
+    /// version of fgdb understands; otherwise each table accepts both the nested and the flat format.
+    pub fn import_dir(&self, dir: &Path, tx: &mut Tx) -> Result<(), FgdbError> {
+        self.import_dir_with_progress(dir, tx, &NoProgress, &CancellationToken::new())
+    }
+
+    /// Imports every table as import_dir does, reporting Progress::Importing to observer as rows are imported.  token
+    /// is checked before each table; once it is cancelled, FgdbError::Cancelled is returned and the remaining tables
+    /// are not imported.
+    pub fn import_dir_with_progress(&self, dir: &Path, tx: &mut Tx, observer: &dyn ProgressObserver, token: &CancellationToken) -> Result<(), FgdbError> {
+        Manifest::read(dir)?;
         for table in TABLES.iter() {
-            let paths = Paths::create(tx.game_variant, Env::Prod);
-            let src_path = paths.lookup(Dir::CacheJson).join(format!("{}.json", table.name()));
+            token.check()?;
+            let src_path = dir.join(format!("{}.json", table.name()));
+            let file = File::open(&src_path)?;
+            let mut reader = BufReader::new(file);
+            let name = table.name();
+            table.import_with_progress(&mut reader, tx, &mut |rows, total| {
+                observer.on_progress(&Progress::Importing { table: name.to_string(), rows, total })
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the rows of the tables named by names with the rows in the JSON files in dir.  Other tables are left
+    /// unchanged.
+    pub fn reimport_tables(&self, dir: &Path, names: &[&str], tx: &mut Tx) -> Result<(), FgdbError> {
+        Manifest::read(dir)?;
+        for table in TABLES.iter().filter(|t| names.contains(&t.name())) {
+            table.delete(tx)?;
+            let src_path = dir.join(format!("{}.json", table.name()));
             let file = File::open(&src_path)?;
             let mut reader = BufReader::new(file);
             table.import(&mut reader, tx)?;
@@ -77,3 +128,58 @@ impl Database {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use crate::db::conn::Conn;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_export_and_import_dir_work() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let db = Database::new().unwrap();
+        let counts = TABLES.iter().map(|t| t.count(&mut tx).unwrap()).collect::<Vec<_>>();
+
+        let dir = Paths::new(env_guard.env).lookup(Dir::EnvOut).join("test_export_and_import_dir_work");
+        db.export(&dir, FormatVersion::Flat, &mut tx).unwrap();
+        assert_eq!(FormatVersion::Flat, Manifest::read(&dir).unwrap().format_version);
+
+        db.delete(&mut tx).unwrap();
+        db.import_dir(&dir, &mut tx).unwrap();
+        assert_eq!(counts, TABLES.iter().map(|t| t.count(&mut tx).unwrap()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_import_dir_with_progress_reports_and_cancels() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let db = Database::new().unwrap();
+        let dir = Paths::new(env_guard.env).lookup(Dir::EnvOut).join("test_import_dir_with_progress");
+        db.export(&dir, FormatVersion::Flat, &mut tx).unwrap();
+        db.delete(&mut tx).unwrap();
+
+        let reports = Mutex::new(Vec::new());
+        let observer = |progress: &Progress| reports.lock().unwrap().push(progress.clone());
+        db.import_dir_with_progress(&dir, &mut tx, &observer, &CancellationToken::new()).unwrap();
+        for table in TABLES.iter() {
+            let total = table.count(&mut tx).unwrap();
+            if total > 0 {
+                let name = table.name().to_string();
+                assert!(reports.lock().unwrap().contains(&Progress::Importing { table: name, rows: total, total }));
+            }
+        }
+
+        // Cancelling during the first table stops the import before the second table.
+        db.delete(&mut tx).unwrap();
+        let token = CancellationToken::new();
+        let observer = |_: &Progress| token.cancel();
+        let result = db.import_dir_with_progress(&dir, &mut tx, &observer, &token);
+        assert!(matches!(result, Err(FgdbError::Cancelled)));
+        assert!(TABLES.iter().skip(1).all(|t| t.count(&mut tx).unwrap() == 0));
+    }
+}
@@ -5,7 +5,11 @@
 #[cfg(feature = "std")]
 pub mod conn;
 #[cfg(feature = "std")]
+pub mod conn_pool;
+#[cfg(feature = "std")]
 pub mod database;
+#[cfg(feature = "std")]
+pub mod row_reader;
 #[cfg(any(feature = "std", feature = "rows"))]
 pub mod rows;
 #[cfg(any(feature = "std", feature = "names"))]
@@ -0,0 +1,117 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::util::errors::{FgdbColumnError, UNKNOWN_COLUMN};
+use rusqlite::types::{FromSql, Type, ValueRef};
+use rusqlite::{Error as RusqliteError, Row};
+
+/// Reads values from a row, attributing conversion failures to the table, column and primary key responsible.  The
+/// primary key of the table must be the first column of the query.
+pub struct RowReader<'a, 'stmt> {
+    table: &'a str,
+    row: &'a Row<'stmt>,
+}
+
+impl<'a, 'stmt> RowReader<'a, 'stmt> {
+    pub fn new(table: &'a str, row: &'a Row<'stmt>) -> Self {
+        Self { table, row }
+    }
+
+    /// Gets the value of the column at index.
+    pub fn get<T: FromSql>(&self, index: usize) -> Result<T, RusqliteError> {
+        self.row.get(index).map_err(|e| self.attribute_error(index, e))
+    }
+
+    /// Attributes a failure to construct a value from one or more columns, e.g., Icon::from_sql, to the column at
+    /// index unless the failure already identifies its column.
+    pub fn attribute<T>(&self, index: usize, result: Result<T, RusqliteError>) -> Result<T, RusqliteError> {
+        result.map_err(|e| self.attribute_error(index, e))
+    }
+
+    fn attribute_error(&self, index: usize, e: RusqliteError) -> RusqliteError {
+        let (index, source): (usize, Box<dyn std::error::Error + Send + Sync>) = match e {
+            RusqliteError::FromSqlConversionFailure(i, _, source) => (if i == UNKNOWN_COLUMN { index } else { i }, source),
+            RusqliteError::IntegralValueOutOfRange(i, value) => (i, format!("integral value {} out of range", value).into()),
+            RusqliteError::InvalidColumnType(i, name, sql_type) => (i, format!("column '{}' cannot be read from SQL type {}", name, sql_type).into()),
+            e => return e,
+        };
+        FgdbColumnError {
+            table: self.table.to_string(),
+            column: self.row.as_ref().column_name(index).unwrap_or_default().to_string(),
+            index,
+            primary_key: self.primary_key(),
+            sql_type: self.row.get_ref(index).map(|v| v.data_type()).unwrap_or(Type::Null),
+            source,
+        }
+        .into()
+    }
+
+    fn primary_key(&self) -> String {
+        match self.row.get_ref(0) {
+            Ok(ValueRef::Text(text)) => String::from_utf8_lossy(text).to_string(),
+            Ok(ValueRef::Integer(i)) => i.to_string(),
+            _ => String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use crate::db::conn::Conn;
+    use crate::db::tables::action_sets_table::ActionSetsTable;
+    use crate::db::tables::base_types_table::BaseTypesTable;
+    use crate::db::tables::table::Table;
+    use crate::types::format_version::FormatVersion;
+    use crate::util::errors::{FgdbError, FgdbFromSqlError, FgdbParseError};
+
+    #[test]
+    fn test_export_reports_column_of_invalid_value() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        tx.execute("UPDATE action_sets SET text_color = 'Black' WHERE action_set = 'Error'", ()).unwrap();
+
+        let error = ActionSetsTable::new().export(&mut Vec::new(), FormatVersion::Nested, &mut tx).unwrap_err();
+        let FgdbError::Column(column_error) = error else { panic!("Expected column error, got {:?}", error) };
+        assert_eq!("action_sets", column_error.table);
+        assert_eq!("text_color", column_error.column);
+        assert_eq!(3, column_error.index);
+        assert_eq!("Error", column_error.primary_key);
+        assert_eq!(Type::Text, column_error.sql_type);
+        assert!(matches!(column_error.source.downcast_ref(), Some(FgdbParseError::InvalidStockColor(_))));
+    }
+
+    #[test]
+    fn test_export_attributes_composite_value_to_first_column() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        tx.execute("UPDATE action_sets SET minimap_icon_shape = NULL WHERE action_set = 'Error'", ()).unwrap();
+
+        let error = ActionSetsTable::new().export(&mut Vec::new(), FormatVersion::Nested, &mut tx).unwrap_err();
+        let FgdbError::Column(column_error) = error else { panic!("Expected column error, got {:?}", error) };
+        assert_eq!("minimap_icon_shape", column_error.column);
+        assert_eq!(7, column_error.index);
+        assert_eq!(Type::Null, column_error.sql_type);
+        assert!(matches!(column_error.source.downcast_ref(), Some(FgdbFromSqlError::Icon(_))));
+    }
+
+    #[test]
+    fn test_export_reports_column_of_invalid_url() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        tx.execute("UPDATE base_types SET url = 'not a url' WHERE base_type = 'Chaos Orb'", ()).unwrap();
+
+        let error = BaseTypesTable::new().export(&mut Vec::new(), FormatVersion::Nested, &mut tx).unwrap_err();
+        let FgdbError::Column(column_error) = error else { panic!("Expected column error, got {:?}", error) };
+        assert_eq!("base_types", column_error.table);
+        assert_eq!("url", column_error.column);
+        assert_eq!(4, column_error.index);
+        assert_eq!("Chaos Orb", column_error.primary_key);
+        assert!(matches!(column_error.source.downcast_ref(), Some(url::ParseError::RelativeUrlWithoutBase)));
+    }
+}
@@ -2,6 +2,7 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 2/20/2026.
 
+use crate::types::item_art::ItemArt;
 use crate::types::liquidity::Liquidity;
 use crate::types::stack_size::StackSize;
 use serde::{Deserialize, Serialize};
@@ -32,3 +33,11 @@ pub struct BaseTypesRow {
     // color for the item.  If the trade API does not provide an image URL for the base type, url is null.
     pub url: Option<Url>,
 }
+
+impl BaseTypesRow {
+    /// Returns the item art encoded in url, or None if url is absent, is not a generated image URL or has an
+    /// undecodable specification.
+    pub fn item_art(&self) -> Option<ItemArt> {
+        self.url.as_ref().and_then(ItemArt::from_url)
+    }
+}
@@ -5,18 +5,19 @@
 use super::macros::*;
 use super::table::GenericTable;
 use super::table::Table;
+use crate::db::row_reader::RowReader;
 use crate::db::rows::action_sets_row::ActionSetsRow;
 use crate::db::tables::names::ACTION_SETS;
 use crate::db::tx::Tx;
+use crate::types::format_version::FormatVersion;
 use crate::types::icon::Icon;
 use crate::types::sound::{Sound, Type};
-use crate::util::consts;
-use anyhow::{Error, Result};
+use crate::util::errors::FgdbError;
+use crate::util::json;
 use itertools::Itertools;
 use paste::paste;
 use rand::Rng;
 use rusqlite::{params, Error as RusqliteError};
-use serde_json_fmt::JsonFormat;
 use std::io;
 use std::io::{Read, Write};
 
@@ -31,7 +32,7 @@ impl Table for ActionSetsTable {
         Self { name: ACTION_SETS.to_string() }
     }
 
-    fn create(&self, tx: &mut Tx) -> Result<(), Error> {
+    fn create(&self, tx: &mut Tx) -> Result<(), FgdbError> {
         tx.execute(
             "CREATE TABLE IF NOT EXISTS action_sets
                 (
@@ -56,10 +57,11 @@ impl Table for ActionSetsTable {
         Ok(())
     }
 
-    fn export(&self, writer: &mut dyn Write, tx: &mut Tx) -> Result<(), Error> {
+    fn export(&self, writer: &mut dyn Write, format_version: FormatVersion, tx: &mut Tx) -> Result<(), FgdbError> {
         let mut stmt = tx.prepare("SELECT * FROM action_sets")?;
         let rows: Vec<ActionSetsRow> = stmt
             .query_map([], |row| {
+                let row = RowReader::new(&self.name, row);
                 Ok(ActionSetsRow {
                     action_set: row.get(0)?,
                     is_template_compatible: row.get(1)?,
@@ -68,18 +70,18 @@ impl Table for ActionSetsTable {
                     border_color: row.get(4)?,
                     play_effect_color: row.get(5)?,
                     background_color: row.get(6)?,
-                    icon: Icon::from_sql(row.get(7)?, row.get(8)?, row.get(9)?)?,
-                    sound: Sound::from_sql(row.get(10)?, row.get(11)?, row.get(12)?)?,
+                    icon: row.attribute(7, Icon::from_sql(row.get(7)?, row.get(8)?, row.get(9)?))?,
+                    sound: row.attribute(10, Sound::from_sql(row.get(10)?, row.get(11)?, row.get(12)?))?,
                 })
             })?
             .collect::<Result<Vec<ActionSetsRow>, RusqliteError>>()?;
-        let json = JsonFormat::pretty().indent_width(Some(consts::JSON_TAB)).ascii(true).format_to_string(&rows)?;
+        let json = json::to_string(&rows, format_version)?;
         writer.write_all(json.as_bytes())?;
         Ok(())
     }
 
-    fn import(&self, reader: &mut dyn Read, tx: &mut Tx) -> Result<(), Error> {
-        let rows: Vec<ActionSetsRow> = serde_json::from_reader(reader)?;
+    fn import_with_progress(&self, reader: &mut dyn Read, tx: &mut Tx, progress: &mut dyn FnMut(usize, usize)) -> Result<(), FgdbError> {
+        let rows: Vec<ActionSetsRow> = json::from_reader(reader)?;
         let mut stmt = tx.prepare_cached(
             r#"INSERT OR IGNORE INTO action_sets (
                 action_set,
@@ -97,7 +99,7 @@ impl Table for ActionSetsTable {
                 custom_sound)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"#,
         )?;
-        for row in &rows {
+        for (index, row) in rows.iter().enumerate() {
             stmt.execute(params![
                 row.action_set,
                 row.is_template_compatible,
@@ -121,6 +123,7 @@ impl Table for ActionSetsTable {
                     None
                 },
             ])?;
+            progress(index + 1, rows.len());
         }
         Ok(())
     }
@@ -5,16 +5,17 @@
 use super::macros::*;
 use super::table::GenericTable;
 use super::table::Table;
+use crate::db::row_reader::RowReader;
 use crate::db::rows::armor_types_row::ArmorTypesRow;
 use crate::db::tables::names::ARMOR_TYPES;
 use crate::db::tx::Tx;
-use crate::util::consts;
-use anyhow::{Error, Result};
+use crate::types::format_version::FormatVersion;
+use crate::util::errors::FgdbError;
+use crate::util::json;
 use itertools::Itertools;
 use paste::paste;
 use rand::Rng;
 use rusqlite::{params, Error as RusqliteError};
-use serde_json_fmt::JsonFormat;
 use std::io;
 use std::io::{Read, Write};
 
@@ -29,7 +30,7 @@ impl Table for ArmorTypesTable {
         Self { name: ARMOR_TYPES.to_string() }
     }
 
-    fn create(&self, tx: &mut Tx) -> Result<(), Error> {
+    fn create(&self, tx: &mut Tx) -> Result<(), FgdbError> {
         tx.execute(
             "CREATE TABLE IF NOT EXISTS armor_types
                 (
@@ -42,26 +43,28 @@ impl Table for ArmorTypesTable {
         Ok(())
     }
 
-    fn export(&self, writer: &mut dyn Write, tx: &mut Tx) -> Result<(), Error> {
+    fn export(&self, writer: &mut dyn Write, format_version: FormatVersion, tx: &mut Tx) -> Result<(), FgdbError> {
         let mut stmt = tx.prepare("SELECT * FROM armor_types")?;
         let rows: Vec<ArmorTypesRow> = stmt
             .query_map([], |row| {
+                let row = RowReader::new(&self.name, row);
                 Ok(ArmorTypesRow {
                     base_type: row.get(0)?,
                     armor_type: row.get(1)?,
                 })
             })?
             .collect::<Result<Vec<ArmorTypesRow>, RusqliteError>>()?;
-        let json = JsonFormat::pretty().indent_width(Some(consts::JSON_TAB)).ascii(true).format_to_string(&rows)?;
+        let json = json::to_string(&rows, format_version)?;
         writer.write_all(json.as_bytes())?;
         Ok(())
     }
 
-    fn import(&self, reader: &mut dyn Read, tx: &mut Tx) -> Result<(), Error> {
-        let rows: Vec<ArmorTypesRow> = serde_json::from_reader(reader)?;
+    fn import_with_progress(&self, reader: &mut dyn Read, tx: &mut Tx, progress: &mut dyn FnMut(usize, usize)) -> Result<(), FgdbError> {
+        let rows: Vec<ArmorTypesRow> = json::from_reader(reader)?;
         let mut stmt = tx.prepare_cached("INSERT OR IGNORE INTO armor_types (base_type, armor_type) VALUES (?1, ?2)")?;
-        for row in &rows {
+        for (index, row) in rows.iter().enumerate() {
             stmt.execute(params![row.base_type, row.armor_type])?;
+            progress(index + 1, rows.len());
         }
         Ok(())
     }
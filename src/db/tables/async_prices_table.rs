@@ -5,17 +5,18 @@
 use super::macros::*;
 use super::table::GenericTable;
 use super::table::Table;
+use crate::db::row_reader::RowReader;
 use crate::db::rows::async_prices_row::AsyncPricesRow;
 use crate::db::rows::base_type_items_row::BaseTypeItemsRow;
 use crate::db::tables::names::ASYNC_PRICES;
 use crate::db::tx::Tx;
-use crate::util::consts;
-use anyhow::{Error, Result};
+use crate::types::format_version::FormatVersion;
+use crate::util::errors::FgdbError;
+use crate::util::json;
 use itertools::Itertools;
 use paste::paste;
 use rand::Rng;
 use rusqlite::{params, Error as RusqliteError};
-use serde_json_fmt::JsonFormat;
 use std::io;
 use std::io::{Read, Write};
 
@@ -32,7 +33,7 @@ impl Table for AsyncPricesTable {
         }
     }
 
-    fn create(&self, tx: &mut Tx) -> Result<(), Error> {
+    fn create(&self, tx: &mut Tx) -> Result<(), FgdbError> {
         tx.execute(
             "CREATE TABLE IF NOT EXISTS async_prices
                 (
@@ -59,10 +60,11 @@ impl Table for AsyncPricesTable {
         Ok(())
     }
 
-    fn export(&self, writer: &mut dyn Write, tx: &mut Tx) -> Result<(), Error> {
+    fn export(&self, writer: &mut dyn Write, format_version: FormatVersion, tx: &mut Tx) -> Result<(), FgdbError> {
         let mut stmt = tx.prepare("SELECT * FROM async_prices")?;
         let rows: Vec<AsyncPricesRow> = stmt
             .query_map([], |row| {
+                let row = RowReader::new(&self.name, row);
                 Ok(AsyncPricesRow {
                     base_type: row.get(2)?,
                     item: row.get(3)?,
@@ -73,15 +75,15 @@ impl Table for AsyncPricesTable {
                 })
             })?
             .collect::<Result<Vec<AsyncPricesRow>, RusqliteError>>()?;
-        let json = JsonFormat::pretty().indent_width(Some(consts::JSON_TAB)).ascii(true).format_to_string(&rows)?;
+        let json = json::to_string(&rows, format_version)?;
         writer.write_all(json.as_bytes())?;
         Ok(())
     }
 
-    fn import(&self, reader: &mut dyn Read, tx: &mut Tx) -> Result<(), Error> {
-        let rows: Vec<AsyncPricesRow> = serde_json::from_reader(reader)?;
+    fn import_with_progress(&self, reader: &mut dyn Read, tx: &mut Tx, progress: &mut dyn FnMut(usize, usize)) -> Result<(), FgdbError> {
+        let rows: Vec<AsyncPricesRow> = json::from_reader(reader)?;
         let mut stmt = tx.prepare_cached("INSERT OR IGNORE INTO async_prices (async_price_key, base_type_item, base_type, item, minimum_item_level, gem_level, rarity, price) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)")?;
-        for row in &rows {
+        for (index, row) in rows.iter().enumerate() {
             stmt.execute(params![
                 row.gen_key(),
                 BaseTypeItemsRow::gen_key_from_parts(&row.base_type, &row.item),
@@ -92,6 +94,7 @@ impl Table for AsyncPricesTable {
                 row.rarity,
                 row.price,
             ])?;
+            progress(index + 1, rows.len());
         }
         Ok(())
     }
@@ -5,16 +5,17 @@
 use super::macros::*;
 use super::table::GenericTable;
 use super::table::Table;
+use crate::db::row_reader::RowReader;
 use crate::db::rows::base_type_items_row::BaseTypeItemsRow;
 use crate::db::tables::names::BASE_TYPE_ITEMS;
 use crate::db::tx::Tx;
-use crate::util::consts;
-use anyhow::{Error, Result};
+use crate::types::format_version::FormatVersion;
+use crate::util::errors::FgdbError;
+use crate::util::json;
 use itertools::Itertools;
 use paste::paste;
 use rand::Rng;
 use rusqlite::{params, Error as RusqliteError};
-use serde_json_fmt::JsonFormat;
 use std::io;
 use std::io::{Read, Write};
 
@@ -31,7 +32,7 @@ impl Table for BaseTypeItemsTable {
         }
     }
 
-    fn create(&self, tx: &mut Tx) -> Result<(), Error> {
+    fn create(&self, tx: &mut Tx) -> Result<(), FgdbError> {
         tx.execute(
             "CREATE TABLE IF NOT EXISTS base_type_items
                 (
@@ -52,10 +53,11 @@ impl Table for BaseTypeItemsTable {
         Ok(())
     }
 
-    fn export(&self, writer: &mut dyn Write, tx: &mut Tx) -> Result<(), Error> {
+    fn export(&self, writer: &mut dyn Write, format_version: FormatVersion, tx: &mut Tx) -> Result<(), FgdbError> {
         let mut stmt = tx.prepare("SELECT * FROM base_type_items")?;
         let rows: Vec<BaseTypeItemsRow> = stmt
             .query_map([], |row| {
+                let row = RowReader::new(&self.name, row);
                 Ok(BaseTypeItemsRow {
                     base_type: row.get(1)?,
                     item: row.get(2)?,
@@ -63,21 +65,22 @@ impl Table for BaseTypeItemsTable {
                 })
             })?
             .collect::<Result<Vec<BaseTypeItemsRow>, RusqliteError>>()?;
-        let json = JsonFormat::pretty().indent_width(Some(consts::JSON_TAB)).ascii(true).format_to_string(&rows)?;
+        let json = json::to_string(&rows, format_version)?;
         writer.write_all(json.as_bytes())?;
         Ok(())
     }
 
-    fn import(&self, reader: &mut dyn Read, tx: &mut Tx) -> Result<(), Error> {
-        let rows: Vec<BaseTypeItemsRow> = serde_json::from_reader(reader)?;
+    fn import_with_progress(&self, reader: &mut dyn Read, tx: &mut Tx, progress: &mut dyn FnMut(usize, usize)) -> Result<(), FgdbError> {
+        let rows: Vec<BaseTypeItemsRow> = json::from_reader(reader)?;
         let mut stmt = tx.prepare_cached("INSERT OR IGNORE INTO base_type_items (base_type_item, base_type, item, is_unique) VALUES (?1, ?2, ?3, ?4)")?;
-        for row in &rows {
+        for (index, row) in rows.iter().enumerate() {
             stmt.execute(params![
                 BaseTypeItemsRow::gen_key_from_parts(&row.base_type, &row.item),
                 row.base_type,
                 row.item,
                 row.is_unique,
             ])?;
+            progress(index + 1, rows.len());
         }
         Ok(())
     }
@@ -5,17 +5,19 @@
 use super::macros::*;
 use super::table::GenericTable;
 use super::table::Table;
+use crate::db::row_reader::RowReader;
 use crate::db::rows::base_types_row::BaseTypesRow;
 use crate::db::tables::names::BASE_TYPES;
 use crate::db::tx::Tx;
-use crate::util::consts;
-use anyhow::{Error, Result};
+use crate::types::format_version::FormatVersion;
+use crate::types::item_art::ItemArt;
+use crate::util::errors::FgdbError;
+use crate::util::json;
 use itertools::Itertools;
 use paste::paste;
 use rand::Rng;
 use rusqlite::types::Type;
-use rusqlite::{params, Error as RusqliteError};
-use serde_json_fmt::JsonFormat;
+use rusqlite::{params, Error as RusqliteError, OptionalExtension};
 use std::io;
 use std::io::{Read, Write};
 use url::Url;
@@ -24,6 +26,15 @@ pub struct BaseTypesTable {
     pub name: String,
 }
 
+// The item art columns and their definitions.  Databases created before item art was decoded lack these columns.
+const ITEM_ART_COLUMNS: [(&str, &str); 5] = [
+    ("art", "art TEXT"),
+    ("width", "width INTEGER CHECK (width >= 1)"),
+    ("height", "height INTEGER CHECK (height >= 1)"),
+    ("scale", "scale INTEGER CHECK (scale >= 1)"),
+    ("is_fractured", "is_fractured INTEGER CHECK (is_fractured IN (0, 1))"),
+];
+
 impl_generic_table!(BaseTypes);
 
 impl Table for BaseTypesTable {
@@ -31,7 +42,7 @@ impl Table for BaseTypesTable {
         Self { name: BASE_TYPES.to_string() }
     }
 
-    fn create(&self, tx: &mut Tx) -> Result<(), Error> {
+    fn create(&self, tx: &mut Tx) -> Result<(), FgdbError> {
         tx.execute(
             "CREATE TABLE IF NOT EXISTS base_types
                 (
@@ -39,46 +50,206 @@ impl Table for BaseTypesTable {
                     class        TEXT    NOT NULL,
                     stack_size   INTEGER NOT NULL               CHECK (stack_size > 0),
                     liquidity    TEXT    NOT NULL,
-                    url          TEXT
+                    url          TEXT,
+
+                    -- Item art decoded from url.  These columns are null if url is null or is not a generated image
+                    -- URL.  Width and height are measured in inventory cells.
+                    art          TEXT,
+                    width        INTEGER                        CHECK (width >= 1),
+                    height       INTEGER                        CHECK (height >= 1),
+                    scale        INTEGER                        CHECK (scale >= 1),
+                    is_fractured INTEGER                        CHECK (is_fractured IN (0, 1))
                 ) STRICT",
             (),
         )?;
-        Ok(())
+        self.add_item_art_columns(tx)
     }
 
-    fn export(&self, writer: &mut dyn Write, tx: &mut Tx) -> Result<(), Error> {
-        let mut stmt = tx.prepare("SELECT * FROM base_types")?;
+    fn export(&self, writer: &mut dyn Write, format_version: FormatVersion, tx: &mut Tx) -> Result<(), FgdbError> {
+        let mut stmt = tx.prepare("SELECT base_type, class, stack_size, liquidity, url FROM base_types")?;
         let rows: Vec<BaseTypesRow> = stmt
             .query_map([], |row| {
+                let row = RowReader::new(&self.name, row);
                 Ok(BaseTypesRow {
                     base_type: row.get(0)?,
                     class: row.get(1)?,
                     stack_size: row.get(2)?,
                     liquidity: row.get(3)?,
-                    url: row
-                        .get::<usize, Option<String>>(4)?
-                        .map(|s| Url::parse(&s).map_err(|e| RusqliteError::FromSqlConversionFailure(4, Type::Text, Box::new(e))))
-                        .transpose()?,
+                    url: row.attribute(
+                        4,
+                        row.get::<Option<String>>(4)?
+                            .map(|s| Url::parse(&s).map_err(|e| RusqliteError::FromSqlConversionFailure(4, Type::Text, Box::new(e))))
+                            .transpose(),
+                    )?,
                 })
             })?
             .collect::<Result<Vec<BaseTypesRow>, RusqliteError>>()?;
-        let json = JsonFormat::pretty().indent_width(Some(consts::JSON_TAB)).ascii(true).format_to_string(&rows)?;
+        let json = json::to_string(&rows, format_version)?;
         writer.write_all(json.as_bytes())?;
         Ok(())
     }
 
-    fn import(&self, reader: &mut dyn Read, tx: &mut Tx) -> Result<(), Error> {
-        let rows: Vec<BaseTypesRow> = serde_json::from_reader(reader)?;
-        let mut stmt = tx.prepare_cached("INSERT OR IGNORE INTO base_types (base_type, class, stack_size, liquidity, url) VALUES (?1, ?2, ?3, ?4, ?5)")?;
-        for row in &rows {
+    fn import_with_progress(&self, reader: &mut dyn Read, tx: &mut Tx, progress: &mut dyn FnMut(usize, usize)) -> Result<(), FgdbError> {
+        let rows: Vec<BaseTypesRow> = json::from_reader(reader)?;
+        let mut stmt = tx.prepare_cached(
+            r#"INSERT OR IGNORE INTO base_types (
+                base_type,
+                class,
+                stack_size,
+                liquidity,
+                url,
+                art,
+                width,
+                height,
+                scale,
+                is_fractured)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"#,
+        )?;
+        for (index, row) in rows.iter().enumerate() {
+            let item_art = row.item_art();
             stmt.execute(params![
                 row.base_type,
                 row.class,
                 row.stack_size,
                 row.liquidity,
                 row.url.as_ref().map(|u| u.as_str()),
+                item_art.as_ref().map(|a| a.art.as_str()),
+                item_art.as_ref().map(|a| a.width),
+                item_art.as_ref().map(|a| a.height),
+                item_art.as_ref().map(|a| a.scale),
+                item_art.as_ref().map(|a| a.is_fractured),
             ])?;
+            progress(index + 1, rows.len());
         }
         Ok(())
     }
 }
+
+impl BaseTypesTable {
+    // Adds any missing item art columns to a base_types table created with an earlier schema and decodes the item art
+    // of its rows from their URLs.
+    fn add_item_art_columns(&self, tx: &mut Tx) -> Result<(), FgdbError> {
+        let columns = tx
+            .prepare("SELECT name FROM pragma_table_info('base_types')")?
+            .query_map([], |row| row.get::<usize, String>(0))?
+            .collect::<Result<Vec<String>, RusqliteError>>()?;
+        let missing = ITEM_ART_COLUMNS.iter().filter(|(name, _)| !columns.iter().any(|column| column == name)).collect::<Vec<_>>();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        for (_, definition) in missing {
+            tx.execute(&format!("ALTER TABLE base_types ADD COLUMN {}", definition), ())?;
+        }
+
+        let urls = tx
+            .prepare("SELECT base_type, url FROM base_types WHERE url IS NOT NULL")?
+            .query_map([], |row| Ok((row.get::<usize, String>(0)?, row.get::<usize, String>(1)?)))?
+            .collect::<Result<Vec<(String, String)>, RusqliteError>>()?;
+        let mut stmt = tx.prepare("UPDATE base_types SET art = ?2, width = ?3, height = ?4, scale = ?5, is_fractured = ?6 WHERE base_type = ?1")?;
+        for (base_type, url) in urls {
+            let Some(item_art) = Url::parse(&url).ok().as_ref().and_then(ItemArt::from_url) else {
+                continue;
+            };
+            stmt.execute(params![base_type, item_art.art, item_art.width, item_art.height, item_art.scale, item_art.is_fractured])?;
+        }
+        Ok(())
+    }
+
+    /// Returns the item art for base_type, or None if the base type has no item art or does not exist.
+    pub fn item_art(&self, base_type: &str, tx: &mut Tx) -> Result<Option<ItemArt>, FgdbError> {
+        let item_art = tx
+            .query_row(
+                "SELECT art, width, height, scale, is_fractured FROM base_types WHERE base_type = ?1",
+                params![base_type],
+                |row| ItemArt::from_sql(row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?),
+            )
+            .optional()?;
+        Ok(item_art.flatten())
+    }
+
+    /// Returns the names of base types whose item art occupies at least min_area inventory cells, in name order.
+    pub fn base_types_with_min_area(&self, min_area: u16, tx: &mut Tx) -> Result<Vec<String>, FgdbError> {
+        let mut stmt = tx.prepare("SELECT base_type FROM base_types WHERE width * height >= ?1 ORDER BY base_type")?;
+        let base_types = stmt
+            .query_map(params![min_area], |row| row.get::<usize, String>(0))?
+            .collect::<Result<Vec<String>, RusqliteError>>()?;
+        Ok(base_types)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use crate::config::fgdb_config::get_config;
+    use crate::db::conn::Conn;
+    use crate::types::game_variant::GameVariant;
+
+    #[test]
+    fn test_item_art_works() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let table = BaseTypesTable::new();
+
+        let (two_by_three, without_art) = match get_config().game_variant {
+            GameVariant::Poe1 => ("Plate Vest", "The Fortunate"),
+            GameVariant::Poe2 => ("Full Plate", "Gold"),
+        };
+        let item_art = table.item_art(two_by_three, &mut tx).unwrap().unwrap();
+        assert_eq!((2, 3), (item_art.width, item_art.height));
+        assert_eq!(None, table.item_art(without_art, &mut tx).unwrap());
+        assert_eq!(None, table.item_art("No Such Base Type", &mut tx).unwrap());
+    }
+
+    #[test]
+    fn test_base_types_with_min_area_works() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let table = BaseTypesTable::new();
+
+        let large = table.base_types_with_min_area(6, &mut tx).unwrap();
+        assert!(!large.is_empty());
+        for base_type in &large {
+            assert!(table.item_art(base_type, &mut tx).unwrap().unwrap().area() >= 6);
+        }
+        assert!(large.len() < table.base_types_with_min_area(1, &mut tx).unwrap().len());
+    }
+
+    #[test]
+    fn test_create_adds_item_art_columns_to_earlier_schema() {
+        let mut conn = Conn::in_memory(GameVariant::Poe1).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        tx.execute(
+            "CREATE TABLE base_types
+                (
+                    base_type  TEXT    NOT NULL PRIMARY KEY,
+                    class      TEXT    NOT NULL,
+                    stack_size INTEGER NOT NULL               CHECK (stack_size > 0),
+                    liquidity  TEXT    NOT NULL,
+                    url        TEXT
+                ) STRICT",
+            (),
+        )
+        .unwrap();
+        let url = concat!(
+            "https://web.poecdn.com/gen/image/",
+            "WzI1LDE0LHsiZiI6IjJESXRlbXMvQXJtb3Vycy9Cb2R5QXJtb3Vycy9Cb2R5RGV4M0MiLCJ3IjoyLCJoIjozLCJzY2FsZSI6MSwiZnJhY3R1cmVkIjp0cnVlfV0",
+            "/ea8240a665/BodyDex3C.png"
+        );
+        tx.execute("INSERT INTO base_types VALUES ('Plate Vest', 'Body Armours', 1, 'Liquid', ?1), ('Gold', 'Currency', 1, 'Liquid', NULL)", params![url])
+            .unwrap();
+        let table = BaseTypesTable::new();
+
+        table.create(&mut tx).unwrap();
+        let item_art = table.item_art("Plate Vest", &mut tx).unwrap().unwrap();
+        assert_eq!((2, 3, true), (item_art.width, item_art.height, item_art.is_fractured));
+        assert_eq!(None, table.item_art("Gold", &mut tx).unwrap());
+        assert_eq!(vec!["Plate Vest".to_string()], table.base_types_with_min_area(6, &mut tx).unwrap());
+
+        // Creating the table again leaves it unchanged.
+        table.create(&mut tx).unwrap();
+        assert_eq!(item_art, table.item_art("Plate Vest", &mut tx).unwrap().unwrap());
+    }
+}
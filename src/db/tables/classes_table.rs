@@ -5,16 +5,17 @@
 use super::macros::*;
 use super::table::GenericTable;
 use super::table::Table;
+use crate::db::row_reader::RowReader;
 use crate::db::rows::classes_row::ClassesRow;
 use crate::db::tables::names::CLASSES;
 use crate::db::tx::Tx;
-use crate::util::consts;
-use anyhow::{Error, Result};
+use crate::types::format_version::FormatVersion;
+use crate::util::errors::FgdbError;
+use crate::util::json;
 use itertools::Itertools;
 use paste::paste;
 use rand::Rng;
 use rusqlite::{params, Error as RusqliteError};
-use serde_json_fmt::JsonFormat;
 use std::io;
 use std::io::{Read, Write};
 
@@ -29,7 +30,7 @@ impl Table for ClassesTable {
         Self { name: CLASSES.to_string() }
     }
 
-    fn create(&self, tx: &mut Tx) -> Result<(), Error> {
+    fn create(&self, tx: &mut Tx) -> Result<(), FgdbError> {
         tx.execute(
             "CREATE TABLE IF NOT EXISTS classes
                 (
@@ -41,26 +42,28 @@ impl Table for ClassesTable {
         Ok(())
     }
 
-    fn export(&self, writer: &mut dyn Write, tx: &mut Tx) -> Result<(), Error> {
+    fn export(&self, writer: &mut dyn Write, format_version: FormatVersion, tx: &mut Tx) -> Result<(), FgdbError> {
         let mut stmt = tx.prepare("SELECT * FROM classes")?;
         let rows: Vec<ClassesRow> = stmt
             .query_map([], |row| {
+                let row = RowReader::new(&self.name, row);
                 Ok(ClassesRow {
                     class: row.get(0)?,
                     highest_rarity: row.get(1)?,
                 })
             })?
             .collect::<Result<Vec<ClassesRow>, RusqliteError>>()?;
-        let json = JsonFormat::pretty().indent_width(Some(consts::JSON_TAB)).ascii(true).format_to_string(&rows)?;
+        let json = json::to_string(&rows, format_version)?;
         writer.write_all(json.as_bytes())?;
         Ok(())
     }
 
-    fn import(&self, reader: &mut dyn Read, tx: &mut Tx) -> Result<(), Error> {
-        let rows: Vec<ClassesRow> = serde_json::from_reader(reader)?;
+    fn import_with_progress(&self, reader: &mut dyn Read, tx: &mut Tx, progress: &mut dyn FnMut(usize, usize)) -> Result<(), FgdbError> {
+        let rows: Vec<ClassesRow> = json::from_reader(reader)?;
         let mut stmt = tx.prepare_cached("INSERT OR IGNORE INTO classes (class, highest_rarity) VALUES (?1, ?2)")?;
-        for row in &rows {
+        for (index, row) in rows.iter().enumerate() {
             stmt.execute(params![row.class, row.highest_rarity])?;
+            progress(index + 1, rows.len());
         }
         Ok(())
     }
@@ -5,17 +5,18 @@
 use super::macros::*;
 use super::table::GenericTable;
 use super::table::Table;
+use crate::db::row_reader::RowReader;
 use crate::db::rows::licenses_row::LicensesRow;
 use crate::db::tables::names::LICENSES;
 use crate::db::tx::Tx;
-use crate::util::consts;
-use anyhow::{Error, Result};
+use crate::types::format_version::FormatVersion;
+use crate::util::errors::FgdbError;
+use crate::util::json;
 use itertools::Itertools;
 use paste::paste;
 use rand::Rng;
 use rusqlite::types::Type;
 use rusqlite::{params, Error as RusqliteError};
-use serde_json_fmt::JsonFormat;
 use std::io;
 use std::io::{Read, Write};
 use url::Url;
@@ -31,7 +32,7 @@ impl Table for LicensesTable {
         Self { name: LICENSES.to_string() }
     }
 
-    fn create(&self, tx: &mut Tx) -> Result<(), Error> {
+    fn create(&self, tx: &mut Tx) -> Result<(), FgdbError> {
         tx.execute(
             "CREATE TABLE IF NOT EXISTS licenses
                 (
@@ -43,26 +44,28 @@ impl Table for LicensesTable {
         Ok(())
     }
 
-    fn export(&self, writer: &mut dyn Write, tx: &mut Tx) -> Result<(), Error> {
+    fn export(&self, writer: &mut dyn Write, format_version: FormatVersion, tx: &mut Tx) -> Result<(), FgdbError> {
         let mut stmt = tx.prepare("SELECT * FROM licenses")?;
         let rows: Vec<LicensesRow> = stmt
             .query_map([], |row| {
+                let row = RowReader::new(&self.name, row);
                 Ok(LicensesRow {
                     license: row.get(0)?,
-                    url: Url::parse(&row.get::<usize, String>(1)?).map_err(|e| RusqliteError::FromSqlConversionFailure(1, Type::Text, Box::new(e)))?,
+                    url: row.attribute(1, Url::parse(&row.get::<String>(1)?).map_err(|e| RusqliteError::FromSqlConversionFailure(1, Type::Text, Box::new(e))))?,
                 })
             })?
             .collect::<Result<Vec<LicensesRow>, RusqliteError>>()?;
-        let json = JsonFormat::pretty().indent_width(Some(consts::JSON_TAB)).ascii(true).format_to_string(&rows)?;
+        let json = json::to_string(&rows, format_version)?;
         writer.write_all(json.as_bytes())?;
         Ok(())
     }
 
-    fn import(&self, reader: &mut dyn Read, tx: &mut Tx) -> Result<(), Error> {
-        let rows: Vec<LicensesRow> = serde_json::from_reader(reader)?;
+    fn import_with_progress(&self, reader: &mut dyn Read, tx: &mut Tx, progress: &mut dyn FnMut(usize, usize)) -> Result<(), FgdbError> {
+        let rows: Vec<LicensesRow> = json::from_reader(reader)?;
         let mut stmt = tx.prepare_cached("INSERT OR IGNORE INTO licenses (license, url) VALUES (?1, ?2)")?;
-        for row in &rows {
+        for (index, row) in rows.iter().enumerate() {
             stmt.execute(params![row.license, row.url.to_string()])?;
+            progress(index + 1, rows.len());
         }
         Ok(())
     }
@@ -7,19 +7,20 @@ macro_rules! impl_generic_table {
     ($table:ty) => {
         paste! {
             impl GenericTable for [<$table Table>] {
-                fn count(&self, tx: &mut Tx) -> Result<usize, Error> {
+                fn count(&self, tx: &mut Tx) -> Result<usize, FgdbError> {
                     let query = format!("SELECT COUNT(*) FROM {};", stringify!([<$table:snake>]));
+                    // COUNT(*) is never negative, so the cast cannot lose information.
                     let count: i64 = tx.query_row(&query, params![], |row| row.get::<usize, i64>(0))?;
-                    Ok(count.try_into()?)
+                    Ok(count as usize)
                 }
 
-                fn delete(&self, tx: &mut Tx) -> Result<usize, Error> {
+                fn delete(&self, tx: &mut Tx) -> Result<usize, FgdbError> {
                     let stmt = format!("DELETE FROM {};", stringify!([<$table:snake>]));
                     let count: usize = tx.execute(&stmt, ())?;
                     Ok(count)
                 }
 
-                fn drop_table(&self, tx: &mut Tx) -> Result<(), Error> {
+                fn drop_table(&self, tx: &mut Tx) -> Result<(), FgdbError> {
                     let stmt = format!("DROP TABLE IF EXISTS {};", stringify!([<$table:snake>]));
                     tx.execute(&stmt, ())?;
                     Ok(())
@@ -29,8 +30,8 @@ macro_rules! impl_generic_table {
                     &self.name
                 }
 
-                fn partial(&self, reader: &mut dyn Read, writer: &mut dyn Write) -> Result<(), Error> {
-                    let rows: Vec<[<$table Row>]> = serde_json::from_reader(reader)?;
+                fn partial(&self, reader: &mut dyn Read, writer: &mut dyn Write) -> Result<(), FgdbError> {
+                    let rows: Vec<[<$table Row>]> = json::from_reader(reader)?;
                     let mut rng = rand::rng();
                     let divisor: i32;
                     if rows.len() < 2 {
@@ -46,10 +47,7 @@ macro_rules! impl_generic_table {
                         .sorted()
                         .dedup()
                         .collect();
-                    let serialized = JsonFormat::pretty()
-                        .indent_width(Some(consts::JSON_TAB))
-                        .ascii(true)
-                        .format_to_string(&filtered_rows)?;
+                    let serialized = json::to_string(&filtered_rows, FormatVersion::Nested)?;
                     let mut reader = serialized.as_bytes();
                     io::copy(&mut reader, writer)?;
                     Ok(())
@@ -12,4 +12,7 @@ pub const COLORS: &str = "colors";
 pub const CRAFTING_CATEGORIES: &str = "crafting_categories";
 pub const EXCHANGE_PRICES: &str = "exchange_prices";
 pub const LICENSES: &str = "licenses";
+pub const PRICE_REFRESHES: &str = "price_refreshes";
 pub const SOUNDS: &str = "sounds";
+pub const UPDATE_LOG: &str = "update_log";
+pub const UPDATE_LOG_ROW_COUNTS: &str = "update_log_row_counts";
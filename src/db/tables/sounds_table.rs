@@ -5,16 +5,17 @@
 use super::macros::*;
 use super::table::GenericTable;
 use super::table::Table;
+use crate::db::row_reader::RowReader;
 use crate::db::rows::sounds_row::SoundsRow;
 use crate::db::tables::names::SOUNDS;
 use crate::db::tx::Tx;
-use crate::util::consts;
-use anyhow::{Error, Result};
+use crate::types::format_version::FormatVersion;
+use crate::util::errors::FgdbError;
+use crate::util::json;
 use itertools::Itertools;
 use paste::paste;
 use rand::Rng;
 use rusqlite::{params, Error as RusqliteError};
-use serde_json_fmt::JsonFormat;
 use std::io;
 use std::io::{Read, Write};
 
@@ -29,7 +30,7 @@ impl Table for SoundsTable {
         Self { name: SOUNDS.to_string() }
     }
 
-    fn create(&self, tx: &mut Tx) -> Result<(), Error> {
+    fn create(&self, tx: &mut Tx) -> Result<(), FgdbError> {
         tx.execute(
             "CREATE TABLE IF NOT EXISTS sounds
                 (
@@ -48,10 +49,11 @@ impl Table for SoundsTable {
         Ok(())
     }
 
-    fn export(&self, writer: &mut dyn Write, tx: &mut Tx) -> Result<(), Error> {
+    fn export(&self, writer: &mut dyn Write, format_version: FormatVersion, tx: &mut Tx) -> Result<(), FgdbError> {
         let mut stmt = tx.prepare("SELECT * FROM sounds")?;
         let rows: Vec<SoundsRow> = stmt
             .query_map([], |row| {
+                let row = RowReader::new(&self.name, row);
                 Ok(SoundsRow {
                     sound: row.get(0)?,
                     file_name: row.get(1)?,
@@ -64,13 +66,13 @@ impl Table for SoundsTable {
                 })
             })?
             .collect::<Result<Vec<SoundsRow>, RusqliteError>>()?;
-        let json = JsonFormat::pretty().indent_width(Some(consts::JSON_TAB)).ascii(true).format_to_string(&rows)?;
+        let json = json::to_string(&rows, format_version)?;
         writer.write_all(json.as_bytes())?;
         Ok(())
     }
 
-    fn import(&self, reader: &mut dyn Read, tx: &mut Tx) -> Result<(), Error> {
-        let rows: Vec<SoundsRow> = serde_json::from_reader(reader)?;
+    fn import_with_progress(&self, reader: &mut dyn Read, tx: &mut Tx, progress: &mut dyn FnMut(usize, usize)) -> Result<(), FgdbError> {
+        let rows: Vec<SoundsRow> = json::from_reader(reader)?;
         let mut stmt = tx.prepare_cached(
             r#"INSERT OR IGNORE INTO sounds 
                 (
@@ -85,7 +87,7 @@ impl Table for SoundsTable {
                 ) 
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"#,
         )?;
-        for row in &rows {
+        for (index, row) in rows.iter().enumerate() {
             stmt.execute(params![
                 row.sound,
                 row.file_name,
@@ -96,6 +98,7 @@ impl Table for SoundsTable {
                 row.url,
                 row.license
             ])?;
+            progress(index + 1, rows.len());
         }
         Ok(())
     }
@@ -3,22 +3,23 @@
 // Created by Hankinsohl on 1/18/2026.
 
 use crate::db::tx::Tx;
-use anyhow::{Error, Result};
+use crate::types::format_version::FormatVersion;
+use crate::util::errors::FgdbError;
 use std::io::{Read, Write};
 
 /// Trait for the generic methods of the Table interface.
 pub trait GenericTable {
     /// Returns the number of rows in the table.
-    fn count(&self, tx: &mut Tx) -> Result<usize, Error>;
+    fn count(&self, tx: &mut Tx) -> Result<usize, FgdbError>;
 
     /// Deletes all rows in the table and returns the number of rows deleted.
-    fn delete(&self, tx: &mut Tx) -> Result<usize, Error>;
+    fn delete(&self, tx: &mut Tx) -> Result<usize, FgdbError>;
 
     /// Drops the table if it exists.
-    fn drop_table(&self, tx: &mut Tx) -> Result<(), Error>;
+    fn drop_table(&self, tx: &mut Tx) -> Result<(), FgdbError>;
 
     /// Returns true if the table is empty; false otherwise.
-    fn is_empty(&self, tx: &mut Tx) -> Result<bool, Error> {
+    fn is_empty(&self, tx: &mut Tx) -> Result<bool, FgdbError> {
         Ok(self.count(tx)? == 0)
     }
 
@@ -27,7 +28,7 @@ pub trait GenericTable {
 
     /// Reads JSON from reader, randomly removes elements, sorts the remaining elements and then writes the resulting
     /// collection to writer.  The purpose of this method is to generate sorted, partial JSON for use in unit testing.
-    fn partial(&self, reader: &mut dyn Read, writer: &mut dyn Write) -> Result<(), Error>;
+    fn partial(&self, reader: &mut dyn Read, writer: &mut dyn Write) -> Result<(), FgdbError>;
 }
 
 /// Trait for the full Table interface.  Note that Table requires implementation of GenericTable.
@@ -38,13 +39,19 @@ pub trait Table: GenericTable + Send + Sync {
         Self: Sized;
 
     /// Creates the table.
-    fn create(&self, tx: &mut Tx) -> Result<(), Error>;
+    fn create(&self, tx: &mut Tx) -> Result<(), FgdbError>;
 
-    /// Exports all data in the table to writer as JSON.
-    fn export(&self, writer: &mut dyn Write, tx: &mut Tx) -> Result<(), Error>;
+    /// Exports all data in the table to writer as JSON in the given format.
+    fn export(&self, writer: &mut dyn Write, format_version: FormatVersion, tx: &mut Tx) -> Result<(), FgdbError>;
 
-    /// Imports JSON from reader.
-    fn import(&self, reader: &mut dyn Read, tx: &mut Tx) -> Result<(), Error>;
+    /// Imports JSON from reader.  Both the nested and the flat format are accepted.
+    fn import(&self, reader: &mut dyn Read, tx: &mut Tx) -> Result<(), FgdbError> {
+        self.import_with_progress(reader, tx, &mut |_, _| {})
+    }
+
+    /// Imports JSON from reader as import does, calling progress with the number of rows imported so far and the total
+    /// number of rows after each row is imported.
+    fn import_with_progress(&self, reader: &mut dyn Read, tx: &mut Tx, progress: &mut dyn FnMut(usize, usize)) -> Result<(), FgdbError>;
 }
 
 #[cfg(test)]
@@ -222,7 +229,7 @@ mod tests {
             {
                 let file = OpenOptions::new().write(true).create(true).open(&export_path).unwrap();
                 let mut writer = BufWriter::new(file);
-                table.export(&mut writer, tx).unwrap();
+                table.export(&mut writer, FormatVersion::Nested, tx).unwrap();
             }
             let asset_path = env_paths.lookup(Dir::AssetsJsonTest).join(format!("{}.json", table.name()));
             let result = compare_text_files(&asset_path, &export_path, None);
@@ -250,7 +257,7 @@ mod tests {
             {
                 let file = OpenOptions::new().write(true).create(true).open(&export_path).unwrap();
                 let mut writer = BufWriter::new(file);
-                table.export(&mut writer, tx).unwrap();
+                table.export(&mut writer, FormatVersion::Nested, tx).unwrap();
             }
 
             // Finally, compare the exported JSON against the imported JSON.
@@ -259,6 +266,40 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_import_works_with_flat_format() {
+        call_for_each_table(|table, expected_count, tx, env| {
+            // Export the table using the flat format and then delete all data in the table.
+            let env_paths = Paths::new(env);
+            let flat_path = env_paths.lookup(Dir::EnvOut).join(format!("test_import_works_with_flat_format_{}.json", table.name()));
+            {
+                let file = OpenOptions::new().write(true).create(true).truncate(true).open(&flat_path).unwrap();
+                let mut writer = BufWriter::new(file);
+                table.export(&mut writer, FormatVersion::Flat, tx).unwrap();
+            }
+            table.delete(tx).unwrap();
+
+            // Import the flat data and confirm that all rows are restored.
+            {
+                let file = File::open(&flat_path).unwrap();
+                let mut reader = BufReader::new(file);
+                table.import(&mut reader, tx).unwrap();
+            }
+            assert_eq!(expected_count, table.count(tx).unwrap(), "Incorrect count for table {} following flat import.", table.name());
+
+            // Finally, export the data using the nested format and compare it against the test data.
+            let export_path = env_paths.lookup(Dir::EnvOut).join(format!("test_import_works_with_flat_format_nested_{}.json", table.name()));
+            {
+                let file = OpenOptions::new().write(true).create(true).truncate(true).open(&export_path).unwrap();
+                let mut writer = BufWriter::new(file);
+                table.export(&mut writer, FormatVersion::Nested, tx).unwrap();
+            }
+            let asset_path = env_paths.lookup(Dir::AssetsJsonTest).join(format!("{}.json", table.name()));
+            let result = compare_text_files(&asset_path, &export_path, None);
+            assert!(result.is_ok(), "{}-{}: {}", get_config().game_variant, env, result.unwrap_err());
+        });
+    }
+
     #[test]
     fn test_is_empty_works() {
         call_for_each_table(|table, _, tx, _| {
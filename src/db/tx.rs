@@ -3,6 +3,7 @@
 // Created by Hankinsohl on 3/1/2026.
 
 use crate::util::env::Env;
+use crate::util::errors::FgdbError;
 use crate::GameVariant;
 use rusqlite::Transaction;
 use std::ops::Deref;
@@ -19,3 +20,11 @@ impl<'a> Deref for Tx<'a> {
         &self.tx
     }
 }
+
+impl Tx<'_> {
+    /// Commits the transaction.
+    pub fn commit(self) -> Result<(), FgdbError> {
+        self.tx.commit()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::db::rows::licenses_row::LicensesRow;
+use crate::db::rows::sounds_row::SoundsRow;
+use std::fmt::Write;
+
+// Credit for a single sound included in a sound pack.
+#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Attribution {
+    pub sound: SoundsRow,
+    pub license: LicensesRow,
+}
+
+/// Renders attributions as plain text, one indented block per sound.
+pub fn to_text(attributions: &[Attribution]) -> String {
+    let mut text = String::from("Sound Credits\n");
+    for a in attributions {
+        let s = &a.sound;
+        // Writing to a String cannot fail.
+        let _ = write!(
+            text,
+            "\n{} ({})\n    Composer: {}\n    Source: {} ({})\n    Original file: {}\n    Modified: {}\n    License: {} ({})\n",
+            s.sound,
+            s.file_name,
+            s.composer,
+            s.source,
+            s.url,
+            s.original_file_name,
+            if s.is_modified { "Yes" } else { "No" },
+            a.license.license,
+            a.license.url
+        );
+    }
+    text
+}
+
+/// Renders attributions as a Markdown table.
+pub fn to_markdown(attributions: &[Attribution]) -> String {
+    let mut markdown = String::from("# Sound Credits\n\n");
+    markdown.push_str("| Sound | File | Composer | Source | Original File | Modified | License |\n");
+    markdown.push_str("|---|---|---|---|---|---|---|\n");
+    for a in attributions {
+        let s = &a.sound;
+        let _ = writeln!(
+            markdown,
+            "| {} | {} | {} | [{}]({}) | {} | {} | [{}]({}) |",
+            escape(&s.sound),
+            escape(&s.file_name),
+            escape(&s.composer),
+            escape(&s.source),
+            s.url,
+            escape(&s.original_file_name),
+            if s.is_modified { "Yes" } else { "No" },
+            escape(&a.license.license),
+            a.license.url
+        );
+    }
+    markdown
+}
+
+// Escapes characters having special meaning within a Markdown table cell or link text.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('|', "\\|").replace('[', "\\[").replace(']', "\\]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use url::Url;
+
+    fn create_attribution(composer: &str, is_modified: bool) -> Attribution {
+        Attribution {
+            sound: SoundsRow {
+                sound: "Alarm".to_string(),
+                file_name: "alarm.wav".to_string(),
+                original_file_name: "316835__lalks__alarm-01-long.wav".to_string(),
+                source: "freesound".to_string(),
+                composer: composer.to_string(),
+                is_modified,
+                url: "https://freesound.org/people/Lalks/sounds/316835/".to_string(),
+                license: "Creative Commons Attribution 3.0".to_string(),
+            },
+            license: LicensesRow {
+                license: "Creative Commons Attribution 3.0".to_string(),
+                url: Url::parse("https://creativecommons.org/licenses/by/3.0/").unwrap(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_to_text_works() {
+        let text = to_text(&[create_attribution("Lalks", true)]);
+        assert!(text.contains("Alarm (alarm.wav)"));
+        assert!(text.contains("Composer: Lalks"));
+        assert!(text.contains("Modified: Yes"));
+        assert!(text.contains("License: Creative Commons Attribution 3.0 (https://creativecommons.org/licenses/by/3.0/)"));
+    }
+
+    #[test]
+    fn test_to_markdown_escapes_cells() {
+        let markdown = to_markdown(&[create_attribution("A|B", false)]);
+        assert!(markdown.contains("| A\\|B |"));
+        assert!(markdown.contains("[freesound](https://freesound.org/people/Lalks/sounds/316835/)"));
+        assert!(markdown.contains("| No |"));
+    }
+}
@@ -0,0 +1,6 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+pub mod attribution;
+pub mod sound_pack_exporter;
@@ -0,0 +1,252 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::config::fgdb_config::try_get_config;
+use crate::db::rows::action_sets_row::ActionSetsRow;
+use crate::db::rows::licenses_row::LicensesRow;
+use crate::db::rows::sounds_row::SoundsRow;
+use crate::db::tx::Tx;
+use crate::export::attribution;
+use crate::export::attribution::Attribution;
+use crate::types::sound::Type;
+use crate::util::consts;
+use crate::util::errors::{FgdbError, FgdbSoundPackError};
+use rusqlite::types::Type as SqlType;
+use rusqlite::{Error as RusqliteError, OptionalExtension, params};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use url::Url;
+
+// The files written by a sound pack export.
+#[derive(Debug)]
+pub struct SoundPack {
+    // The directory containing the sound files and credits.
+    pub dir: PathBuf,
+
+    // The sound files copied into dir, in sound name order.
+    pub sound_files: Vec<PathBuf>,
+
+    // The plain text credits file.
+    pub text_credits: PathBuf,
+
+    // The Markdown credits file.
+    pub markdown_credits: PathBuf,
+}
+
+/// Assembles the custom sounds used by a set of action sets into a folder alongside a filter, together with credits
+/// for each sound.
+pub struct SoundPackExporter {
+    sound_dir: PathBuf,
+}
+
+impl SoundPackExporter {
+    /// Creates an exporter which obtains sound files from the directory configured by sound_path in fgdb.toml.
+    pub fn new() -> Result<Self, FgdbError> {
+        let sound_dir = try_get_config()?.sound_path.clone().ok_or(FgdbSoundPackError::MissingSoundDir())?;
+        Ok(SoundPackExporter::create(sound_dir))
+    }
+
+    /// Creates an exporter which obtains sound files from sound_dir.
+    pub fn create(sound_dir: PathBuf) -> Self {
+        Self { sound_dir }
+    }
+
+    /// Copies the custom sound files referenced by action_sets into the sound pack directory next to filter_path and
+    /// writes text and Markdown credits for them.  Every sound, sound file and license is checked before anything is
+    /// written; if any is missing, an error is returned and no files are written.
+    pub fn export(&self, action_sets: &[ActionSetsRow], filter_path: &Path, tx: &mut Tx) -> Result<SoundPack, FgdbError> {
+        let sounds: BTreeSet<&str> = action_sets
+            .iter()
+            .filter_map(|a| a.sound.as_ref())
+            .filter(|s| s.sound_type == Type::Custom)
+            .map(|s| s.sound.as_str())
+            .collect();
+
+        let mut attributions = Vec::new();
+        for sound in sounds {
+            let attribution = self.lookup(sound, tx)?;
+
+            // File names come from the repository, so a name which could read or write outside the sound directories
+            // is rejected.
+            let file_name = &attribution.sound.file_name;
+            if !is_plain_file_name(file_name) {
+                return Err(FgdbSoundPackError::InvalidSoundFileName(sound.to_string(), file_name.clone()).into());
+            }
+            let source_path = self.sound_dir.join(&attribution.sound.file_name);
+            if !source_path.is_file() {
+                return Err(FgdbSoundPackError::MissingSoundFile(sound.to_string(), source_path.to_string_lossy().to_string()).into());
+            }
+            attributions.push(attribution);
+        }
+
+        let dir = filter_path.parent().unwrap_or(Path::new("")).join(consts::SOUND_PACK_DIR);
+        fs::create_dir_all(&dir)?;
+        let mut sound_files = Vec::new();
+        for a in &attributions {
+            let destination_path = dir.join(&a.sound.file_name);
+            fs::copy(self.sound_dir.join(&a.sound.file_name), &destination_path)?;
+            sound_files.push(destination_path);
+        }
+
+        let text_credits = dir.join(consts::TEXT_CREDITS_FILE_NAME);
+        fs::write(&text_credits, attribution::to_text(&attributions))?;
+        let markdown_credits = dir.join(consts::MARKDOWN_CREDITS_FILE_NAME);
+        fs::write(&markdown_credits, attribution::to_markdown(&attributions))?;
+
+        Ok(SoundPack {
+            dir,
+            sound_files,
+            text_credits,
+            markdown_credits,
+        })
+    }
+
+    pub fn sound_dir(&self) -> &Path {
+        &self.sound_dir
+    }
+
+    fn lookup(&self, sound: &str, tx: &mut Tx) -> Result<Attribution, FgdbError> {
+        let row = tx
+            .query_row(
+                "SELECT s.sound, s.file_name, s.original_file_name, s.source, s.composer, s.is_modified, s.url, s.license, l.url
+                 FROM sounds s
+                 LEFT JOIN licenses l ON s.license = l.license
+                 WHERE s.sound = ?1",
+                params![sound],
+                |row| {
+                    Ok((
+                        SoundsRow {
+                            sound: row.get(0)?,
+                            file_name: row.get(1)?,
+                            original_file_name: row.get(2)?,
+                            source: row.get(3)?,
+                            composer: row.get(4)?,
+                            is_modified: row.get(5)?,
+                            url: row.get(6)?,
+                            license: row.get(7)?,
+                        },
+                        row.get::<usize, Option<String>>(8)?
+                            .map(|s| Url::parse(&s).map_err(|e| RusqliteError::FromSqlConversionFailure(8, SqlType::Text, Box::new(e))))
+                            .transpose()?,
+                    ))
+                },
+            )
+            .optional()?;
+        match row {
+            None => Err(FgdbSoundPackError::MissingSound(sound.to_string()).into()),
+            Some((sound, None)) => Err(FgdbSoundPackError::MissingLicense(sound.sound, sound.license).into()),
+            Some((sound, Some(url))) => Ok(Attribution {
+                license: LicensesRow {
+                    license: sound.license.clone(),
+                    url,
+                },
+                sound,
+            }),
+        }
+    }
+}
+
+// Returns true if file_name is a single normal path component, i.e., it has no separators and is not a root, prefix, '.'
+// or '..'.
+fn is_plain_file_name(file_name: &str) -> bool {
+    let mut components = Path::new(file_name).components();
+    matches!((components.next(), components.next()), (Some(Component::Normal(name)), None) if *name == *file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use crate::db::conn::Conn;
+    use crate::fs::dir::Dir;
+    use crate::fs::paths::Paths;
+    use crate::types::sound::Sound;
+    use crate::types::stock_color::StockColor;
+    use crate::util::env::Env;
+
+    fn create_action_set(name: &str, custom_sound: Option<&str>) -> ActionSetsRow {
+        let sound = custom_sound.map(|s| Sound::from_sql(None, None, Some(s.to_string())).unwrap().unwrap());
+        ActionSetsRow::builder(name.to_string(), StockColor::White, "Black".to_string(), None, sound).build()
+    }
+
+    fn create_exporter() -> SoundPackExporter {
+        SoundPackExporter::create(Paths::new(Env::Prod).lookup(Dir::AssetsSoundsTest))
+    }
+
+    fn create_filter_path(env: Env, name: &str) -> PathBuf {
+        let filter_dir = Paths::new(env).lookup(Dir::EnvOut).join(name);
+        if filter_dir.exists() {
+            fs::remove_dir_all(&filter_dir).unwrap();
+        }
+        filter_dir.join("test.filter")
+    }
+
+    #[test]
+    fn test_export_works() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let filter_path = create_filter_path(env_guard.env, "test_export_works");
+        let action_sets = [
+            create_action_set("First", Some("Angry Cat")),
+            create_action_set("Second", Some("Alarm")),
+            create_action_set("Duplicate", Some("Alarm")),
+            create_action_set("Silent", None),
+        ];
+
+        let pack = create_exporter().export(&action_sets, &filter_path, &mut tx).unwrap();
+        assert_eq!(filter_path.parent().unwrap().join(consts::SOUND_PACK_DIR), pack.dir);
+        assert_eq!(vec![pack.dir.join("alarm.wav"), pack.dir.join("angry_cat.wav")], pack.sound_files);
+        assert!(pack.sound_files.iter().all(|f| f.is_file()));
+
+        let text = fs::read_to_string(&pack.text_credits).unwrap();
+        assert!(text.contains("Composer: softcoresoft"));
+        let markdown = fs::read_to_string(&pack.markdown_credits).unwrap();
+        assert!(markdown.contains("| Alarm | alarm.wav | Lalks |"));
+    }
+
+    #[test]
+    fn test_export_fails_for_missing_sound_file() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let filter_path = create_filter_path(env_guard.env, "test_export_fails_for_missing_sound_file");
+
+        let result = create_exporter().export(&[create_action_set("Wood", Some("Wood Hit"))], &filter_path, &mut tx);
+        assert!(matches!(result, Err(FgdbError::SoundPack(FgdbSoundPackError::MissingSoundFile(..)))));
+        assert!(!filter_path.parent().unwrap().exists());
+    }
+
+    #[test]
+    fn test_export_rejects_sound_file_names_outside_sound_dir() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let filter_path = create_filter_path(env_guard.env, "test_export_rejects_sound_file_names_outside_sound_dir");
+
+        for file_name in ["../../alarm.wav", "/etc/passwd", "sub/alarm.wav", ".", ""] {
+            tx.execute("UPDATE sounds SET file_name = ?1 WHERE sound = 'Alarm'", params![file_name]).unwrap();
+            let result = create_exporter().export(&[create_action_set("Alarm", Some("Alarm"))], &filter_path, &mut tx);
+            assert!(matches!(result, Err(FgdbError::SoundPack(FgdbSoundPackError::InvalidSoundFileName(..)))), "{}", file_name);
+            assert!(!filter_path.parent().unwrap().exists());
+        }
+    }
+
+    #[test]
+    fn test_export_fails_for_missing_sound_or_license() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let filter_path = create_filter_path(env_guard.env, "test_export_fails_for_missing_sound_or_license");
+
+        let result = create_exporter().export(&[create_action_set("Unknown", Some("No Such Sound"))], &filter_path, &mut tx);
+        assert!(matches!(result, Err(FgdbError::SoundPack(FgdbSoundPackError::MissingSound(..)))));
+
+        tx.execute("DELETE FROM licenses WHERE license = 'Creative Commons Attribution 3.0'", ())
+            .unwrap();
+        let result = create_exporter().export(&[create_action_set("Alarm", Some("Alarm"))], &filter_path, &mut tx);
+        assert!(matches!(result, Err(FgdbError::SoundPack(FgdbSoundPackError::MissingLicense(..)))));
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::util::consts;
+use crate::util::errors::FgdbError;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::OnceLock;
+
+static DATA_ROOT: OnceLock<PathBuf> = OnceLock::new();
+static TEST_ENV_ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+/// Sets the data root, the directory under which the assets, cache and env directories are found.  The data root must
+/// be set before any path is resolved; setting it afterwards to a different directory is an error.
+pub fn set_data_root(path: impl Into<PathBuf>) -> Result<(), FgdbError> {
+    let path = path.into();
+    let root = DATA_ROOT.get_or_init(|| path.clone());
+    if *root != path {
+        return Err(FgdbError::Config(
+            format!(
+                "Data root cannot be changed to '{}'; it is already '{}'.  Call set_data_root before using fgdb.",
+                path.display(),
+                root.display()
+            ),
+            None,
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the data root.  Unless set using set_data_root, the data root is, in order of preference, the directory named
+/// by the FGDB_DATA_ROOT environment variable, the current directory if it contains fgdb.toml, or the fgdb directory
+/// within the platform data directory, e.g., %APPDATA%\fgdb on Windows or ~/.local/share/fgdb on Linux.
+pub fn data_root() -> &'static Path {
+    DATA_ROOT.get_or_init(resolve_data_root)
+}
+
+/// Returns the directory under which test environments are created.  Each process uses its own directory within the
+/// system temporary directory so that concurrently running test binaries do not share environments.
+pub fn test_env_root() -> &'static Path {
+    TEST_ENV_ROOT.get_or_init(|| env::temp_dir().join(format!("{}{}", consts::TEST_ENV_ROOT_PREFIX, process::id())))
+}
+
+fn resolve_data_root() -> PathBuf {
+    if let Some(root) = env::var_os(consts::DATA_ROOT_ENV_VAR) {
+        return PathBuf::from(root);
+    }
+    let current_dir = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    if current_dir.join(consts::CONFIG_TOML).is_file() {
+        return current_dir;
+    }
+    dirs::data_dir().map(|d| d.join(consts::APP_DIR_NAME)).unwrap_or(current_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_root_works() {
+        // Tests run from the crate directory, which contains fgdb.toml.
+        let root = data_root();
+        assert!(root.join(consts::CONFIG_TOML).is_file());
+
+        set_data_root(root).unwrap();
+        assert!(matches!(set_data_root(root.join("elsewhere")), Err(FgdbError::Config(..))));
+    }
+}
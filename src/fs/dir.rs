@@ -6,13 +6,16 @@ use std::fmt;
 use std::fmt::Display;
 use strum_macros::EnumIter;
 
-pub static CACHE_DIRS: [Dir; 3] = [Dir::CacheJson, Dir::CacheTimestamp, Dir::CacheZip];
+pub static CACHE_DIRS: [Dir; 4] = [Dir::CacheImages, Dir::CacheJson, Dir::CacheTimestamp, Dir::CacheZip];
 pub static ENV_DIRS: [Dir; 2] = [Dir::EnvDb, Dir::EnvOut];
 pub static ENV_OUT_DIRS: [Dir; 1] = [Dir::EnvOut];
 
 #[derive(Copy, Clone, Debug, EnumIter, PartialEq)]
 pub enum Dir {
+    AssetsImagesTest,
     AssetsJsonTest,
+    AssetsSoundsTest,
+    CacheImages,
     CacheJson,
     CacheTimestamp,
     CacheZip,
@@ -26,7 +29,10 @@ impl Display for Dir {
             f,
             "{}",
             match self {
+                Dir::AssetsImagesTest => "AssetsImagesTest",
                 Dir::AssetsJsonTest => "AssetsJsonTest",
+                Dir::AssetsSoundsTest => "AssetsSoundsTest",
+                Dir::CacheImages => "CacheImages",
                 Dir::CacheJson => "CacheJson",
                 Dir::CacheTimestamp => "CacheTimestamp",
                 Dir::CacheZip => "CacheZip",
@@ -2,5 +2,6 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 1/17/2026.
 
+pub mod data_root;
 pub mod dir;
 pub mod paths;
@@ -3,64 +3,20 @@
 // Created by Hankinsohl on 1/17/2026.
 
 use crate::config::fgdb_config::get_config;
+use crate::fs::data_root::{data_root, test_env_root};
 use crate::fs::dir::{Dir, CACHE_DIRS, ENV_DIRS, ENV_OUT_DIRS};
 use crate::types::game_variant::GameVariant;
 use crate::util::consts::*;
 use crate::util::env::Env;
-use anyhow::{Error, Result};
-use const_format::formatcp;
+use crate::util::errors::FgdbError;
 use std::fs;
-use std::path::Path;
-
-// Note: 2 macros are used to work around RustRover parsing bug ("attempting to use nonexistent positional argument 1").
-macro_rules! gen_poe1_registry_element {
-    ($root:ident) => {
-        [
-            formatcp!("{ASSETS_DIR}/poe1/{ASSETS_JSON_TEST_DIR}"),
-            formatcp!("{CACHE_DIR}/poe1/{CACHE_JSON_DIR}"),
-            formatcp!("{CACHE_DIR}/poe1/{CACHE_TIMESTAMP_DIR}"),
-            formatcp!("{CACHE_DIR}/poe1/{CACHE_ZIP_DIR}"),
-            formatcp!("{ENV_DIR}/poe1/{}/{ENV_DB_DIR}", $root),
-            formatcp!("{ENV_DIR}/poe1/{}/{ENV_OUT_DIR}", $root),
-        ]
-    };
-}
-macro_rules! gen_poe2_registry_element {
-    ($root:ident) => {
-        [
-            formatcp!("{ASSETS_DIR}/poe2/{ASSETS_JSON_TEST_DIR}"),
-            formatcp!("{CACHE_DIR}/poe2/{CACHE_JSON_DIR}"),
-            formatcp!("{CACHE_DIR}/poe2/{CACHE_TIMESTAMP_DIR}"),
-            formatcp!("{CACHE_DIR}/poe2/{CACHE_ZIP_DIR}"),
-            formatcp!("{ENV_DIR}/poe2/{}/{ENV_DB_DIR}", $root),
-            formatcp!("{ENV_DIR}/poe2/{}/{ENV_OUT_DIR}", $root),
-        ]
-    };
-}
+use std::path::{Path, PathBuf};
 
-// Directory registries ordered by [Env][Dir].  These arrays must be kept in sync with changes to the Env
-// and Dir enums.
-const POE1_REGISTRY: [[&str; 6]; 6] = [
-    gen_poe1_registry_element!(PROD_RELATIVE_PATH),
-    gen_poe1_registry_element!(TEST1_RELATIVE_PATH),
-    gen_poe1_registry_element!(TEST2_RELATIVE_PATH),
-    gen_poe1_registry_element!(TEST3_RELATIVE_PATH),
-    gen_poe1_registry_element!(TEST4_RELATIVE_PATH),
-    gen_poe1_registry_element!(TEST5_RELATIVE_PATH),
-];
-const POE2_REGISTRY: [[&str; 6]; 6] = [
-    gen_poe2_registry_element!(PROD_RELATIVE_PATH),
-    gen_poe2_registry_element!(TEST1_RELATIVE_PATH),
-    gen_poe2_registry_element!(TEST2_RELATIVE_PATH),
-    gen_poe2_registry_element!(TEST3_RELATIVE_PATH),
-    gen_poe2_registry_element!(TEST4_RELATIVE_PATH),
-    gen_poe2_registry_element!(TEST5_RELATIVE_PATH),
-];
-
-#[derive(Copy, Clone)]
+#[derive(Clone, Debug)]
 pub struct Paths {
     env: Env,
     game_variant: GameVariant,
+    root: PathBuf,
 }
 
 impl Paths {
@@ -71,62 +27,118 @@ impl Paths {
 
     /// Creates a Paths object for a specified game variant.  A Paths object facilitates path lookup.
     pub fn create(game_variant: GameVariant, env: Env) -> Self {
-        Self { env, game_variant }
+        Paths::with_root(data_root(), game_variant, env)
     }
 
-    /// Returns the path to the specified directory for the environment.
-    pub fn lookup(&self, dir: Dir) -> &Path {
-        match self.game_variant {
-            GameVariant::Poe1 => Path::new(POE1_REGISTRY[self.env as usize][dir as usize]),
-            GameVariant::Poe2 => Path::new(POE2_REGISTRY[self.env as usize][dir as usize]),
+    /// Creates a Paths object which resolves paths relative to root rather than the data root.
+    pub fn with_root(root: &Path, game_variant: GameVariant, env: Env) -> Self {
+        Self {
+            env,
+            game_variant,
+            root: root.to_path_buf(),
         }
     }
-}
 
-pub fn create_cache_dirs(game_variant: GameVariant) -> Result<(), Error> {
-    create_dirs(game_variant, Env::Prod, CACHE_DIRS.iter())
+    /// Returns the path to the specified directory for the environment.  Assets and cache directories are found under
+    /// the root.  Environment directories are found under the root for the production environment and under the test
+    /// environment root for test environments.
+    pub fn lookup(&self, dir: Dir) -> PathBuf {
+        let game_variant = self.game_variant.to_string();
+        match dir {
+            Dir::AssetsImagesTest => self.root.join(ASSETS_DIR).join(game_variant).join(ASSETS_IMAGES_TEST_DIR),
+            Dir::AssetsJsonTest => self.root.join(ASSETS_DIR).join(game_variant).join(ASSETS_JSON_TEST_DIR),
+            Dir::AssetsSoundsTest => self.root.join(ASSETS_DIR).join(game_variant).join(ASSETS_SOUNDS_TEST_DIR),
+            Dir::CacheImages => self.root.join(CACHE_DIR).join(game_variant).join(CACHE_IMAGES_DIR),
+            Dir::CacheJson => self.root.join(CACHE_DIR).join(game_variant).join(CACHE_JSON_DIR),
+            Dir::CacheTimestamp => self.root.join(CACHE_DIR).join(game_variant).join(CACHE_TIMESTAMP_DIR),
+            Dir::CacheZip => self.root.join(CACHE_DIR).join(game_variant).join(CACHE_ZIP_DIR),
+            Dir::EnvDb => self.env_dir().join(ENV_DB_DIR),
+            Dir::EnvOut => self.env_dir().join(ENV_OUT_DIR),
+        }
+    }
+
+    /// Returns the directory containing the environment's directories.
+    pub fn env_dir(&self) -> PathBuf {
+        let root = match self.env {
+            Env::Prod => &self.root,
+            Env::Test(_) => test_env_root(),
+        };
+        root.join(ENV_DIR).join(self.game_variant.to_string()).join(self.env.relative_path())
+    }
+
+    /// Returns the directory relative to which paths are resolved.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn env(&self) -> Env {
+        self.env
+    }
+
+    pub fn game_variant(&self) -> GameVariant {
+        self.game_variant
+    }
+
+    /// Creates each of dirs which does not already exist.
+    pub fn create_dirs<'a>(&self, dirs: impl IntoIterator<Item=&'a Dir>) -> Result<(), FgdbError> {
+        for dir in dirs {
+            let path = self.lookup(*dir);
+            if !path.exists() {
+                fs::create_dir_all(&path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes each of dirs which exists, along with its contents.
+    pub fn remove_dirs<'a>(&self, dirs: impl IntoIterator<Item=&'a Dir>) -> Result<(), FgdbError> {
+        for dir in dirs {
+            let path = self.lookup(*dir);
+            if path.exists() {
+                fs::remove_dir_all(&path)?;
+            }
+        }
+        Ok(())
+    }
 }
 
-pub fn create_env_dirs(game_variant: GameVariant, env: Env) -> Result<(), Error> {
-    create_dirs(game_variant, env, ENV_DIRS.iter())
+pub fn create_cache_dirs(game_variant: GameVariant) -> Result<(), FgdbError> {
+    Paths::create(game_variant, Env::Prod).create_dirs(CACHE_DIRS.iter())
 }
 
-pub fn create_env_out_dirs(game_variant: GameVariant, env: Env) -> Result<(), Error> {
-    create_dirs(game_variant, env, ENV_OUT_DIRS.iter())
+pub fn create_env_dirs(game_variant: GameVariant, env: Env) -> Result<(), FgdbError> {
+    Paths::create(game_variant, env).create_dirs(ENV_DIRS.iter())
 }
 
-pub fn remove_cache_dirs(game_variant: GameVariant) -> Result<(), Error> {
-    remove_dirs(game_variant, Env::Prod, CACHE_DIRS.iter())
+pub fn create_env_out_dirs(game_variant: GameVariant, env: Env) -> Result<(), FgdbError> {
+    Paths::create(game_variant, env).create_dirs(ENV_OUT_DIRS.iter())
 }
 
-pub fn remove_env_dirs(game_variant: GameVariant, env: Env) -> Result<(), Error> {
-    remove_dirs(game_variant, env, ENV_DIRS.iter())
+pub fn remove_cache_dirs(game_variant: GameVariant) -> Result<(), FgdbError> {
+    Paths::create(game_variant, Env::Prod).remove_dirs(CACHE_DIRS.iter())
 }
 
-pub fn remove_env_out_dirs(game_variant: GameVariant, env: Env) -> Result<(), Error> {
-    remove_dirs(game_variant, env, ENV_OUT_DIRS.iter())
+pub fn remove_env_dirs(game_variant: GameVariant, env: Env) -> Result<(), FgdbError> {
+    Paths::create(game_variant, env).remove_dirs(ENV_DIRS.iter())
 }
 
-fn create_dirs<'a>(game_variant: GameVariant, env: Env, dirs: impl IntoIterator<Item=&'a Dir>) -> Result<(), Error> {
-    let paths = Paths::create(game_variant, env);
-    for dir in dirs {
-        let path = paths.lookup(*dir);
-        if !path.exists() {
-            fs::create_dir_all(path)?;
-        }
-    }
-    Ok(())
+pub fn remove_env_out_dirs(game_variant: GameVariant, env: Env) -> Result<(), FgdbError> {
+    Paths::create(game_variant, env).remove_dirs(ENV_OUT_DIRS.iter())
 }
 
-fn remove_dirs<'a>(game_variant: GameVariant, env: Env, dirs: impl IntoIterator<Item=&'a Dir>) -> Result<(), Error> {
-    let paths = Paths::create(game_variant, env);
-    for dir in dirs {
-        let path = paths.lookup(*dir);
-        if path.exists() {
-            fs::remove_dir_all(path)?;
+/// Returns the name of the cached image file for a base type.  Characters which are not portable in file names, and
+/// underscores, are escaped as an underscore, the character's code point in hex and a second underscore, so distinct
+/// base types always map to distinct file names.
+pub fn image_file_name(base_type: &str) -> String {
+    let mut stem = String::with_capacity(base_type.len());
+    for c in base_type.chars() {
+        if c.is_alphanumeric() || matches!(c, ' ' | '-' | '\'' | '(' | ')') {
+            stem.push(c);
+        } else {
+            stem.push_str(&format!("_{:X}_", c as u32));
         }
     }
-    Ok(())
+    format!("{stem}.{IMAGE_FILE_EXTENSION}")
 }
 
 #[cfg(test)]
@@ -135,24 +147,38 @@ mod tests {
     use crate::fs::dir::Dir;
     use crate::fs::paths::Paths;
     use crate::util::env::Env;
-    use strum::IntoEnumIterator;
 
     #[test]
     fn test_lookup_works() {
-        let registry = match get_config().game_variant {
-            GameVariant::Poe1 => &POE1_REGISTRY,
-            GameVariant::Poe2 => &POE2_REGISTRY,
-        };
-        for env in Env::iter() {
-            for dir in Dir::iter() {
-                assert_eq!(
-                    Paths::new(env).lookup(dir).to_string_lossy(),
-                    registry[env as usize][dir as usize],
-                    "Expected '{}': Actual '{}'",
-                    Paths::new(env).lookup(dir).to_string_lossy(),
-                    registry[env as usize][dir as usize]
-                );
-            }
-        }
+        let paths = Paths::new(Env::Prod);
+        let game_variant = get_config().game_variant.to_string();
+        assert_eq!(data_root().join("assets").join(&game_variant).join("json/test"), paths.lookup(Dir::AssetsJsonTest));
+        assert_eq!(data_root().join("cache").join(&game_variant).join("zip"), paths.lookup(Dir::CacheZip));
+        assert_eq!(data_root().join("env").join(&game_variant).join("prod/db"), paths.lookup(Dir::EnvDb));
+    }
+
+    #[test]
+    fn test_with_root_works() {
+        let paths = Paths::with_root(Path::new("/data"), GameVariant::Poe2, Env::Prod);
+        assert_eq!(Path::new("/data/env/poe2/prod/db"), paths.lookup(Dir::EnvDb));
+        assert_eq!(Path::new("/data/cache/poe2/json"), paths.lookup(Dir::CacheJson));
+    }
+
+    #[test]
+    fn test_test_env_dirs_are_under_test_env_root() {
+        let paths = Paths::with_root(Path::new("/data"), GameVariant::Poe2, Env::Test(7));
+        assert_eq!(test_env_root().join("env/poe2/test7/out"), paths.lookup(Dir::EnvOut));
+        assert_eq!(Path::new("/data/assets/poe2/json/test"), paths.lookup(Dir::AssetsJsonTest));
+    }
+
+    #[test]
+    fn test_image_file_name_works() {
+        assert_eq!("Plate Vest.png", image_file_name("Plate Vest"));
+        assert_eq!("Waystone (Tier 1).png", image_file_name("Waystone (Tier 1)"));
+        assert_eq!("A_2F_B_3A_C.png", image_file_name("A/B:C"));
+
+        // Names which differ only in escaped characters map to distinct files.
+        let names = ["A/B", "A:B", "A?B", "A_B", "A_2F_B"].map(image_file_name);
+        assert!(names.iter().enumerate().all(|(i, name)| !names[i + 1..].contains(name)));
     }
 }
@@ -0,0 +1,294 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+#[cfg(feature = "async")]
+use crate::concurrency::blocking_task::{spawn_blocking, BlockingTask};
+#[cfg(feature = "async")]
+use crate::concurrency::cancellation_token::CancellationToken;
+use crate::config::fgdb_config::FgdbConfig;
+use crate::db::conn::Conn;
+use crate::db::conn_pool::ConnPool;
+use crate::db::database::Database;
+#[cfg(feature = "async")]
+use crate::db::tx::Tx;
+use crate::export::sound_pack_exporter::SoundPackExporter;
+use crate::fs::data_root::data_root;
+use crate::fs::dir::{CACHE_DIRS, ENV_DIRS, ENV_OUT_DIRS};
+use crate::fs::paths::Paths;
+use crate::repo::repository::{create_repository, Repository};
+use crate::types::game_variant::GameVariant;
+#[cfg(feature = "async")]
+use crate::update::policy::Policy;
+use crate::update::price_refresher::{PriceRefresher, PriceRefresherBuilder};
+#[cfg(feature = "async")]
+use crate::update::progress::ProgressObserver;
+use crate::update::updater::Updater;
+use crate::util::env::Env;
+use crate::util::errors::{FgdbError, FgdbSoundPackError};
+use std::path::{Path, PathBuf};
+#[cfg(feature = "async")]
+use std::sync::Arc;
+
+/// A handle to one game variant and environment of the database.  Everything the handle creates is located using its
+/// own configuration and data root, so handles for different game variants or environments may be used side by side
+/// in one process.
+#[derive(Clone, Debug)]
+pub struct Fgdb {
+    config: FgdbConfig,
+    paths: Paths,
+
+    // If true, updaters import bundles without verification when the configuration sets no public keys.
+    allow_unsigned: bool,
+}
+
+impl Fgdb {
+    /// Returns a builder for a handle using config.  Unless changed using the builder, the handle uses the game variant
+    /// of config, the production environment and the data root.
+    pub fn builder(config: FgdbConfig) -> FgdbBuilder {
+        FgdbBuilder {
+            game_variant: config.game_variant,
+            config,
+            data_root: None,
+            env: Env::Prod,
+            allow_unsigned: false,
+        }
+    }
+
+    pub fn config(&self) -> &FgdbConfig {
+        &self.config
+    }
+
+    /// Opens a connection to the handle's database.
+    pub fn conn(&self) -> Result<Conn, FgdbError> {
+        Conn::with_paths(&self.paths)
+    }
+
+    /// Creates a connection pool for the handle's database.
+    pub fn conn_pool(&self) -> Result<ConnPool, FgdbError> {
+        ConnPool::with_paths(self.paths.clone())
+    }
+
+    pub fn env(&self) -> Env {
+        self.paths.env()
+    }
+
+    pub fn game_variant(&self) -> GameVariant {
+        self.paths.game_variant()
+    }
+
+    /// Creates the handle's directories and any missing tables.  Existing tables are left unchanged and test data is
+    /// not loaded.
+    pub fn init(&self) -> Result<(), FgdbError> {
+        self.prod_paths().create_dirs(CACHE_DIRS.iter())?;
+        self.paths.create_dirs(ENV_DIRS.iter().chain(ENV_OUT_DIRS.iter()))?;
+        let mut conn = self.conn()?;
+        let mut tx = conn.create_tx()?;
+        Database::new()?.create(&mut tx)
+    }
+
+    pub fn paths(&self) -> &Paths {
+        &self.paths
+    }
+
+    /// Returns a builder for a refresher which keeps the prices in the production database current.
+    pub fn price_refresher(&self) -> PriceRefresherBuilder {
+        PriceRefresher::builder(self.updater())
+    }
+
+    /// Returns the repository named by the configuration: the local repository at root_path or the remote repository
+    /// at repository_url.
+    pub fn repository(&self) -> Result<Box<dyn Repository>, FgdbError> {
+        create_repository(&self.config, self.prod_paths())
+    }
+
+    pub fn root(&self) -> &Path {
+        self.paths.root()
+    }
+
+    /// Returns an exporter for the configuration's sound_path.
+    pub fn sound_pack_exporter(&self) -> Result<SoundPackExporter, FgdbError> {
+        let sound_dir = self.config.sound_path.clone().ok_or(FgdbSoundPackError::MissingSoundDir())?;
+        Ok(SoundPackExporter::create(sound_dir))
+    }
+
+    /// Returns an updater for the handle's game variant.  The updater always updates the production database.
+    pub fn updater(&self) -> Updater {
+        Updater::with_config(self.config.clone(), self.prod_paths()).allow_unsigned(self.allow_unsigned)
+    }
+
+    /// Runs query in a transaction on a new connection to the handle's database on the blocking worker pool.  The
+    /// transaction is rolled back when query returns.
+    #[cfg(feature = "async")]
+    pub fn query_async<T, F>(&self, query: F) -> BlockingTask<Result<T, FgdbError>>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Tx) -> Result<T, FgdbError> + Send + 'static,
+    {
+        let paths = self.paths.clone();
+        spawn_blocking(move || {
+            let mut conn = Conn::with_paths(&paths)?;
+            let mut tx = conn.create_rollback_tx()?;
+            query(&mut tx)
+        })
+    }
+
+    /// Updates the production database according to policy on the blocking worker pool.  See Updater::update_with_progress.
+    #[cfg(feature = "async")]
+    pub fn update_async(&self, policy: Policy, observer: Arc<dyn ProgressObserver>, token: CancellationToken) -> BlockingTask<Result<bool, FgdbError>> {
+        self.updater().update_async(policy, observer, token)
+    }
+
+    // The cache directories are shared by all environments and are located through the production environment.
+    fn prod_paths(&self) -> Paths {
+        Paths::with_root(self.paths.root(), self.paths.game_variant(), Env::Prod)
+    }
+}
+
+// Builder for Fgdb.
+#[derive(Debug)]
+pub struct FgdbBuilder {
+    config: FgdbConfig,
+
+    data_root: Option<PathBuf>,
+
+    env: Env,
+
+    game_variant: GameVariant,
+
+    allow_unsigned: bool,
+}
+
+impl FgdbBuilder {
+    pub fn build(self) -> Fgdb {
+        let root = match self.data_root {
+            Some(root) => root,
+            None => data_root().to_path_buf(),
+        };
+        Fgdb {
+            paths: Paths::with_root(&root, self.game_variant, self.env),
+            config: FgdbConfig {
+                game_variant: self.game_variant,
+                ..self.config
+            },
+            allow_unsigned: self.allow_unsigned,
+        }
+    }
+
+    /// Sets whether the handle's updaters import bundles without verifying their signatures when the configuration
+    /// sets no public keys.  See Updater::allow_unsigned.  Unsigned bundles are refused by default.
+    pub fn allow_unsigned(mut self, allow_unsigned: bool) -> FgdbBuilder {
+        self.allow_unsigned = allow_unsigned;
+        self
+    }
+
+    /// Sets the directory under which the handle's assets, cache and env directories are found.
+    pub fn data_root(mut self, data_root: impl Into<PathBuf>) -> FgdbBuilder {
+        self.data_root = Some(data_root.into());
+        self
+    }
+
+    pub fn env(mut self, env: Env) -> FgdbBuilder {
+        self.env = env;
+        self
+    }
+
+    pub fn game_variant(mut self, game_variant: GameVariant) -> FgdbBuilder {
+        self.game_variant = game_variant;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use crate::db::tables::classes_table::ClassesTable;
+    use crate::db::tables::table::{GenericTable, Table};
+    use crate::fs::dir::Dir;
+    use crate::update::policy::Policy;
+    use crate::util::consts;
+    use rusqlite::params;
+    use std::fs;
+
+    fn config() -> FgdbConfig {
+        FgdbConfig::local(PathBuf::from("repository"), GameVariant::Poe1)
+    }
+
+    #[test]
+    fn test_handles_for_different_game_variants_coexist() {
+        let env_guard = EnvPoolGuard::new();
+        let root = env_guard.out_dir("test_handles_for_different_game_variants_coexist");
+        let poe1 = Fgdb::builder(config()).data_root(&root).build();
+        let poe2 = Fgdb::builder(config()).data_root(&root).game_variant(GameVariant::Poe2).build();
+        poe1.init().unwrap();
+        poe2.init().unwrap();
+
+        assert_eq!(root.join("env/poe1/prod/db"), poe1.paths().lookup(Dir::EnvDb));
+        assert_eq!(root.join("env/poe2/prod/db"), poe2.paths().lookup(Dir::EnvDb));
+        assert_eq!(GameVariant::Poe2, poe2.config().game_variant);
+        assert_eq!(GameVariant::Poe2, poe2.updater().game_variant);
+        assert_eq!(root.join("cache/poe1/json"), poe1.updater().paths.lookup(Dir::CacheJson));
+
+        let mut conn = poe1.conn().unwrap();
+        let tx = conn.create_tx().unwrap();
+        tx.execute("INSERT INTO classes (class) VALUES (?1);", params!["Amulets"]).unwrap();
+        drop(tx);
+
+        let table = ClassesTable::new();
+        assert_eq!(1, table.count(&mut poe1.conn().unwrap().create_tx().unwrap()).unwrap());
+        assert_eq!(0, table.count(&mut poe2.conn().unwrap().create_tx().unwrap()).unwrap());
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_query_async_works() {
+        use crate::concurrency::blocking_task::tests::block_on;
+        use crate::config::fgdb_config::get_config;
+        use crate::update::progress::NoProgress;
+
+        let env_guard = EnvPoolGuard::new();
+        let fgdb = Fgdb::builder(config()).game_variant(get_config().game_variant).env(env_guard.env).build();
+        let count = block_on(fgdb.query_async(|tx| ClassesTable::new().count(tx))).unwrap();
+        assert!(count > 0);
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(matches!(block_on(fgdb.update_async(Policy::Force, Arc::new(NoProgress), token)), Err(FgdbError::Cancelled)));
+    }
+
+    #[test]
+    fn test_allow_unsigned_is_passed_to_updaters() {
+        let env_guard = EnvPoolGuard::new();
+        let root = env_guard.out_dir("test_allow_unsigned_is_passed_to_updaters");
+        let repository_path = root.join("repository").join(GameVariant::Poe1.to_string());
+        fs::create_dir_all(repository_path.join(consts::REPOSITORY_ZIP_DIR)).unwrap();
+        fs::create_dir_all(repository_path.join(consts::REPOSITORY_TIMESTAMP_DIR)).unwrap();
+        fs::write(repository_path.join(consts::REPOSITORY_TIMESTAMP_DIR).join(consts::TIMESTAMP_FILE_NAME), "{}").unwrap();
+        fs::write(repository_path.join(consts::REPOSITORY_ZIP_DIR).join(consts::REPOSITORY_ZIP_FILE_NAME), b"not a zip file").unwrap();
+        let config = FgdbConfig {
+            root_path: root.join("repository"),
+            ..config()
+        };
+        let fgdb = Fgdb::builder(config.clone()).data_root(&root).build();
+        fgdb.init().unwrap();
+
+        // Without keys the bundle is refused before it is extracted unless unsigned bundles are allowed.
+        assert!(matches!(fgdb.updater().update(Policy::Force), Err(FgdbError::Config(..))));
+        let fgdb = Fgdb::builder(config).data_root(&root).allow_unsigned(true).build();
+        assert!(matches!(fgdb.updater().update(Policy::Force), Err(FgdbError::Repository(..))));
+    }
+
+    #[test]
+    fn test_sound_pack_exporter_requires_sound_path() {
+        let fgdb = Fgdb::builder(config()).data_root("/data").build();
+        assert!(matches!(fgdb.sound_pack_exporter(), Err(FgdbError::SoundPack(FgdbSoundPackError::MissingSoundDir()))));
+
+        let config = FgdbConfig {
+            sound_path: Some(PathBuf::from("/sounds")),
+            ..config()
+        };
+        let fgdb = Fgdb::builder(config).data_root("/data").build();
+        assert_eq!(Path::new("/sounds"), fgdb.sound_pack_exporter().unwrap().sound_dir());
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::db::rows::colors_row::ColorsRow;
+use crate::db::tx::Tx;
+use crate::fs::dir::Dir;
+use crate::fs::paths;
+use crate::fs::paths::Paths;
+use crate::image::color_histogram::ColorHistogram;
+use crate::types::color_basis::ColorBasis;
+use crate::util::env::Env;
+use crate::util::errors::FgdbError;
+use rgb::RGB8 as Rgb8;
+use rusqlite::{Error as RusqliteError, params};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// The method used to reduce an image to a single color.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColorMethod {
+    // The alpha-weighted average of all pixels.
+    Average,
+
+    // The mean color of the most common group of similar pixels.
+    Dominant,
+}
+
+// The outcome of extracting colors.
+#[derive(Debug, Default)]
+pub struct ColorExtractionReport {
+    // The colors extracted, in name order.
+    pub rows: Vec<ColorsRow>,
+
+    // Base types whose cached image could not be read, together with the reason.  Their images are skipped.
+    pub failed: Vec<(String, FgdbError)>,
+}
+
+/// Derives background colors for drops from cached base type images and records them in the colors table.
+pub struct ColorExtractor {
+    image_dir: Option<PathBuf>,
+    method: ColorMethod,
+}
+
+impl ColorExtractor {
+    /// Creates an extractor which reads images from the cache images directory of the transaction's game variant.
+    pub fn new(method: ColorMethod) -> Self {
+        Self { image_dir: None, method }
+    }
+
+    /// Creates an extractor which reads images from image_dir.
+    pub fn create(image_dir: PathBuf, method: ColorMethod) -> Self {
+        Self {
+            image_dir: Some(image_dir),
+            method,
+        }
+    }
+
+    /// Computes a color for each base type or class, according to basis, having at least one cached image.  The
+    /// resulting rows are named after the base type or class and are returned in name order.  Base types lacking a
+    /// cached image are skipped, as are those whose image cannot be read, which are reported as failed.
+    pub fn extract(&self, basis: ColorBasis, tx: &mut Tx) -> Result<ColorExtractionReport, FgdbError> {
+        let image_dir = match &self.image_dir {
+            Some(dir) => dir.clone(),
+            None => Paths::create(tx.game_variant, Env::Prod).lookup(Dir::CacheImages),
+        };
+
+        let mut stmt = tx.prepare("SELECT base_type, class, url FROM base_types WHERE url IS NOT NULL ORDER BY base_type")?;
+        let base_types = stmt
+            .query_map([], |row| {
+                Ok((row.get::<usize, String>(0)?, row.get::<usize, String>(1)?, row.get::<usize, String>(2)?))
+            })?
+            .collect::<Result<Vec<_>, RusqliteError>>()?;
+
+        // Accumulate a histogram and the URL of the first contributing image for each color name.
+        let mut histograms: BTreeMap<String, (String, ColorHistogram)> = BTreeMap::new();
+        let mut failed = Vec::new();
+        for (base_type, class, url) in base_types {
+            let image_path = image_dir.join(paths::image_file_name(&base_type));
+            if !image_path.exists() {
+                continue;
+            }
+            let histogram = match ColorHistogram::from_png(&image_path) {
+                Ok(histogram) => histogram,
+                Err(e) => {
+                    failed.push((base_type, e));
+                    continue;
+                }
+            };
+            let name = match basis {
+                ColorBasis::BaseType => base_type,
+                ColorBasis::Class => class,
+            };
+            histograms.entry(name).or_insert_with(|| (url, ColorHistogram::new())).1.merge(&histogram);
+        }
+
+        let rows = histograms
+            .into_iter()
+            .filter_map(|(color, (url, histogram))| {
+                self.reduce(&histogram).map(|rgb| ColorsRow {
+                    color,
+                    url,
+                    pixel: rgb.with_alpha(255),
+                })
+            })
+            .collect();
+        Ok(ColorExtractionReport { rows, failed })
+    }
+
+    /// Extracts colors according to basis and writes them to the colors table, replacing existing colors having the
+    /// same name.  The rows of the returned report are the colors written.
+    pub fn update(&self, basis: ColorBasis, tx: &mut Tx) -> Result<ColorExtractionReport, FgdbError> {
+        let report = self.extract(basis, tx)?;
+        let mut stmt = tx.prepare_cached("INSERT OR REPLACE INTO colors (color, url, red, green, blue, alpha) VALUES (?1, ?2, ?3, ?4, ?5, ?6)")?;
+        for row in &report.rows {
+            stmt.execute(params![row.color, row.url, row.pixel.r, row.pixel.g, row.pixel.b, row.pixel.a])?;
+        }
+        Ok(report)
+    }
+
+    fn reduce(&self, histogram: &ColorHistogram) -> Option<Rgb8> {
+        match self.method {
+            ColorMethod::Average => histogram.average(),
+            ColorMethod::Dominant => histogram.dominant(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use crate::config::fgdb_config::get_config;
+    use crate::db::conn::Conn;
+    use crate::db::tables::colors_table::ColorsTable;
+    use crate::db::tables::table::GenericTable;
+    use crate::db::tables::table::Table;
+    use crate::types::game_variant::GameVariant;
+    use rgb::RGBA8 as Rgba8;
+    use std::fs;
+
+    const RED: Rgba8 = Rgba8::new(200, 30, 30, 255);
+    const GOLD: Rgba8 = Rgba8::new(220, 180, 40, 255);
+
+    // Names of the base types having image fixtures, and the class shared by the first two.
+    fn fixture_names() -> (&'static str, &'static str, &'static str) {
+        match get_config().game_variant {
+            GameVariant::Poe1 => ("Plate Vest", "Simple Robe", "Ruby Ring"),
+            GameVariant::Poe2 => ("Full Plate", "Garment", "Lazuli Ring"),
+        }
+    }
+
+    fn create_extractor(method: ColorMethod) -> ColorExtractor {
+        ColorExtractor::create(Paths::new(Env::Prod).lookup(Dir::AssetsImagesTest), method)
+    }
+
+    #[test]
+    fn test_extract_by_base_type_works() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let (armour, _, ring) = fixture_names();
+
+        let rows = create_extractor(ColorMethod::Dominant).extract(ColorBasis::BaseType, &mut tx).unwrap().rows;
+        assert_eq!(3, rows.len());
+        assert_eq!(RED, rows.iter().find(|r| r.color == armour).unwrap().pixel);
+        assert_eq!(GOLD, rows.iter().find(|r| r.color == ring).unwrap().pixel);
+
+        // Transparent pixels must not darken the average.
+        let rows = create_extractor(ColorMethod::Average).extract(ColorBasis::BaseType, &mut tx).unwrap().rows;
+        assert_eq!(RED, rows.iter().find(|r| r.color == armour).unwrap().pixel);
+    }
+
+    #[test]
+    fn test_extract_by_class_works() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+
+        let rows = create_extractor(ColorMethod::Dominant).extract(ColorBasis::Class, &mut tx).unwrap().rows;
+        assert_eq!(vec!["Body Armours", "Rings"], rows.iter().map(|r| r.color.as_str()).collect::<Vec<_>>());
+
+        // Body Armours combines 12 + 6 red pixels with 10 blue pixels.
+        assert_eq!(RED, rows[0].pixel);
+    }
+
+    #[test]
+    fn test_update_works() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let table = ColorsTable::new();
+        let initial_count = table.count(&mut tx).unwrap();
+
+        let report = create_extractor(ColorMethod::Dominant).update(ColorBasis::BaseType, &mut tx).unwrap();
+        assert_eq!(3, report.rows.len());
+        assert_eq!(initial_count + 3, table.count(&mut tx).unwrap());
+
+        // Updating a second time replaces rather than duplicates colors.
+        create_extractor(ColorMethod::Dominant).update(ColorBasis::BaseType, &mut tx).unwrap();
+        assert_eq!(initial_count + 3, table.count(&mut tx).unwrap());
+    }
+
+    #[test]
+    fn test_unreadable_image_is_skipped_and_reported() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let (armour, _, ring) = fixture_names();
+        let image_dir = env_guard.out_dir("test_unreadable_image_is_skipped_and_reported");
+        for entry in fs::read_dir(Paths::new(Env::Prod).lookup(Dir::AssetsImagesTest)).unwrap() {
+            let entry = entry.unwrap();
+            fs::copy(entry.path(), image_dir.join(entry.file_name())).unwrap();
+        }
+        fs::write(image_dir.join(paths::image_file_name(ring)), b"not a png").unwrap();
+
+        let report = ColorExtractor::create(image_dir, ColorMethod::Dominant).extract(ColorBasis::BaseType, &mut tx).unwrap();
+        assert_eq!(2, report.rows.len());
+        assert!(report.rows.iter().any(|r| r.color == armour));
+        assert_eq!(vec![ring], report.failed.iter().map(|(base_type, _)| base_type.as_str()).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::util::errors::{FgdbError, FgdbImageError};
+use png::{ColorType, Decoder, Transformations};
+use rgb::RGB8 as Rgb8;
+use rgb::RGBA8 as Rgba8;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+// Number of low-order bits discarded from each channel when bucketing pixels.  Discarding 4 bits yields 4096 buckets,
+// which is coarse enough to group anti-aliased edges with the surfaces they border.
+const BUCKET_SHIFT: u32 = 4;
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Bucket {
+    weight: f64,
+    red: f64,
+    green: f64,
+    blue: f64,
+}
+
+impl Bucket {
+    fn add(&mut self, other: &Bucket) {
+        self.weight += other.weight;
+        self.red += other.red;
+        self.green += other.green;
+        self.blue += other.blue;
+    }
+
+    fn mean(&self) -> Option<Rgb8> {
+        if self.weight <= 0.0 {
+            return None;
+        }
+        let channel = |sum: f64| (sum / self.weight).round().clamp(0.0, 255.0) as u8;
+        Some(Rgb8::new(channel(self.red), channel(self.green), channel(self.blue)))
+    }
+}
+
+/// A histogram of the colors in one or more images.  Each pixel is weighted by its alpha so that transparent
+/// surroundings do not contribute to the result.
+#[derive(Clone, Debug, Default)]
+pub struct ColorHistogram {
+    buckets: HashMap<u16, Bucket>,
+}
+
+impl ColorHistogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a histogram from the PNG at path.
+    pub fn from_png(path: &Path) -> Result<Self, FgdbError> {
+        let mut decoder = Decoder::new(BufReader::new(File::open(path)?));
+        decoder.set_transformations(Transformations::normalize_to_color8());
+        let invalid_png = |e: png::DecodingError| FgdbImageError::InvalidPng(path.to_string_lossy().to_string(), e.to_string());
+        let mut reader = decoder.read_info().map_err(invalid_png)?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer).map_err(invalid_png)?;
+        let bytes = &buffer[..info.buffer_size()];
+        let pixels: Vec<Rgba8> = match info.color_type {
+            ColorType::Rgba => bytes.chunks_exact(4).map(|p| Rgba8::new(p[0], p[1], p[2], p[3])).collect(),
+            ColorType::Rgb => bytes.chunks_exact(3).map(|p| Rgba8::new(p[0], p[1], p[2], 255)).collect(),
+            ColorType::GrayscaleAlpha => bytes.chunks_exact(2).map(|p| Rgba8::new(p[0], p[0], p[0], p[1])).collect(),
+            ColorType::Grayscale => bytes.iter().map(|&p| Rgba8::new(p, p, p, 255)).collect(),
+            ColorType::Indexed => unreachable!("Indexed color is expanded by Transformations::EXPAND."),
+        };
+        let mut histogram = ColorHistogram::new();
+        histogram.add_pixels(&pixels);
+        Ok(histogram)
+    }
+
+    pub fn add_pixels(&mut self, pixels: &[Rgba8]) {
+        for pixel in pixels.iter().filter(|p| p.a > 0) {
+            let weight = pixel.a as f64 / 255.0;
+            let key = ((pixel.r as u16 >> BUCKET_SHIFT) << 8) | ((pixel.g as u16 >> BUCKET_SHIFT) << 4) | (pixel.b as u16 >> BUCKET_SHIFT);
+            self.buckets.entry(key).or_default().add(&Bucket {
+                weight,
+                red: weight * pixel.r as f64,
+                green: weight * pixel.g as f64,
+                blue: weight * pixel.b as f64,
+            });
+        }
+    }
+
+    /// Returns the alpha-weighted average of all pixels, or None if the histogram contains no opaque pixels.
+    pub fn average(&self) -> Option<Rgb8> {
+        let mut total = Bucket::default();
+        for bucket in self.buckets.values() {
+            total.add(bucket);
+        }
+        total.mean()
+    }
+
+    /// Returns the mean color of the most heavily weighted bucket, or None if the histogram contains no opaque
+    /// pixels.  Ties are broken in favor of the bucket with the lowest key so that results are deterministic.
+    pub fn dominant(&self) -> Option<Rgb8> {
+        self.buckets
+            .iter()
+            .max_by(|(a_key, a), (b_key, b)| a.weight.total_cmp(&b.weight).then(b_key.cmp(a_key)))
+            .and_then(|(_, bucket)| bucket.mean())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    /// Adds the contents of other to this histogram.
+    pub fn merge(&mut self, other: &ColorHistogram) {
+        for (key, bucket) in &other.buckets {
+            self.buckets.entry(*key).or_default().add(bucket);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Rgba8 = Rgba8::new(200, 30, 30, 255);
+    const BLUE: Rgba8 = Rgba8::new(30, 30, 200, 255);
+    const CLEAR: Rgba8 = Rgba8::new(0, 0, 0, 0);
+
+    #[test]
+    fn test_empty_histogram_has_no_color() {
+        let mut histogram = ColorHistogram::new();
+        histogram.add_pixels(&[CLEAR, CLEAR]);
+        assert!(histogram.is_empty());
+        assert_eq!(None, histogram.average());
+        assert_eq!(None, histogram.dominant());
+    }
+
+    #[test]
+    fn test_average_and_dominant_work() {
+        let mut histogram = ColorHistogram::new();
+        histogram.add_pixels(&[RED, RED, RED, BLUE, CLEAR]);
+        assert_eq!(Some(Rgb8::new(200, 30, 30)), histogram.dominant());
+        assert_eq!(Some(Rgb8::new(158, 30, 73)), histogram.average());
+    }
+
+    #[test]
+    fn test_merge_works() {
+        let mut reds = ColorHistogram::new();
+        reds.add_pixels(&[RED]);
+        let mut blues = ColorHistogram::new();
+        blues.add_pixels(&[BLUE, BLUE]);
+        reds.merge(&blues);
+        assert_eq!(Some(Rgb8::new(30, 30, 200)), reds.dominant());
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::fs::paths;
+use crate::image::image_fetcher::ImageFetcher;
+use crate::util::errors::{FgdbError, FgdbImageError};
+use std::fs;
+use std::path::PathBuf;
+use url::Url;
+
+/// Fetches base type images from a local directory containing files named as they are in the image cache.  The
+/// image URL is ignored.
+pub struct DirImageFetcher {
+    source_dir: PathBuf,
+}
+
+impl ImageFetcher for DirImageFetcher {
+    fn fetch(&self, base_type: &str, _url: &Url, max_size: u64) -> Result<Vec<u8>, FgdbError> {
+        let path = self.source_dir.join(paths::image_file_name(base_type));
+        let size = fs::metadata(&path)?.len();
+        if size > max_size {
+            return Err(FgdbImageError::TooLarge(base_type.to_string(), max_size).into());
+        }
+        Ok(fs::read(&path)?)
+    }
+}
+
+impl DirImageFetcher {
+    pub fn new(source_dir: PathBuf) -> Self {
+        Self { source_dir }
+    }
+}
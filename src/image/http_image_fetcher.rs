@@ -0,0 +1,43 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::image::image_fetcher::ImageFetcher;
+use crate::util::errors::{FgdbError, FgdbImageError};
+use crate::util::http;
+use ureq::Agent;
+use url::Url;
+
+/// Fetches base type images from the web using the image URL recorded in the base_types table.
+pub struct HttpImageFetcher {
+    agent: Agent,
+}
+
+impl ImageFetcher for HttpImageFetcher {
+    fn fetch(&self, base_type: &str, url: &Url, max_size: u64) -> Result<Vec<u8>, FgdbError> {
+        let mut response = self.agent.get(url.as_str()).call().map_err(|e| FgdbError::Network(e.to_string(), Some(Box::new(e))))?;
+        match response.body_mut().with_config().limit(max_size).read_to_vec() {
+            Ok(bytes) => Ok(bytes),
+            Err(ureq::Error::BodyExceedsLimit(_)) => Err(FgdbImageError::TooLarge(base_type.to_string(), max_size).into()),
+            Err(e) => Err(FgdbError::Network(e.to_string(), Some(Box::new(e)))),
+        }
+    }
+}
+
+impl Default for HttpImageFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpImageFetcher {
+    /// Creates a fetcher which makes requests with http::default_agent.
+    pub fn new() -> Self {
+        Self::with_agent(http::default_agent())
+    }
+
+    /// Creates a fetcher which makes requests with agent, e.g., one with the timeouts of a configuration.
+    pub fn with_agent(agent: Agent) -> Self {
+        Self { agent }
+    }
+}
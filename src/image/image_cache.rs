@@ -0,0 +1,248 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::db::tx::Tx;
+use crate::fs::dir::Dir;
+use crate::fs::paths;
+use crate::fs::paths::Paths;
+use crate::image::image_fetcher::ImageFetcher;
+use crate::types::game_variant::GameVariant;
+use crate::util::consts;
+use crate::util::env::Env;
+use crate::util::errors::{FgdbError, FgdbImageError, FgdbParseError};
+use png::Decoder;
+use rusqlite::Error as RusqliteError;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+// The outcome of populating the image cache.
+#[derive(Debug, Default)]
+pub struct ImageCacheReport {
+    // The number of images fetched and added to the cache.
+    pub fetched: usize,
+
+    // The number of images already present and valid in the cache.
+    pub cached: usize,
+
+    // Base types whose image could not be cached, together with the reason.
+    pub failed: Vec<(String, FgdbError)>,
+}
+
+/// A cache of base type images stored under cache/<variant>/images, one PNG per base type.
+pub struct ImageCache {
+    dir: PathBuf,
+    fetcher: Box<dyn ImageFetcher>,
+    max_image_size: u64,
+    max_image_dimension: u32,
+}
+
+impl ImageCache {
+    /// Creates an image cache for the specified game variant which obtains missing images using fetcher.
+    pub fn new(game_variant: GameVariant, fetcher: Box<dyn ImageFetcher>) -> Self {
+        ImageCache::create(Paths::create(game_variant, Env::Prod).lookup(Dir::CacheImages), fetcher)
+    }
+
+    /// Creates an image cache in dir which obtains missing images using fetcher.
+    pub fn create(dir: PathBuf, fetcher: Box<dyn ImageFetcher>) -> Self {
+        Self {
+            dir,
+            fetcher,
+            max_image_size: consts::MAX_IMAGE_FILE_SIZE,
+            max_image_dimension: consts::MAX_IMAGE_DIMENSION,
+        }
+    }
+
+    /// Removes cached images for base types no longer present in the database.  Returns the number of files removed.
+    pub fn collect_garbage(&self, tx: &mut Tx) -> Result<usize, FgdbError> {
+        if !self.dir.exists() {
+            return Ok(0);
+        }
+        let mut stmt = tx.prepare("SELECT base_type FROM base_types")?;
+        let expected: HashSet<String> = stmt
+            .query_map([], |row| row.get::<usize, String>(0))?
+            .map(|base_type| base_type.map(|b| paths::image_file_name(&b)))
+            .collect::<Result<HashSet<String>, RusqliteError>>()?;
+
+        let mut removed = 0;
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            if !expected.contains(entry.file_name().to_string_lossy().as_ref()) {
+                fs::remove_file(entry.path())?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the path to the cached image for base_type, or None if the image is not cached.
+    pub fn lookup(&self, base_type: &str) -> Option<PathBuf> {
+        let path = self.dir.join(paths::image_file_name(base_type));
+        path.exists().then_some(path)
+    }
+
+    /// Sets the largest image width or height, in pixels, accepted into the cache.
+    pub fn max_image_dimension(mut self, max_image_dimension: u32) -> Self {
+        self.max_image_dimension = max_image_dimension;
+        self
+    }
+
+    /// Sets the largest image file size, in bytes, accepted into the cache.
+    pub fn max_image_size(mut self, max_image_size: u64) -> Self {
+        self.max_image_size = max_image_size;
+        self
+    }
+
+    /// Ensures that the cache holds a valid image for every base type having an image URL.  Images already cached
+    /// are validated and refetched if invalid.  A failure to cache one image does not prevent caching the others.
+    pub fn populate(&self, tx: &mut Tx) -> Result<ImageCacheReport, FgdbError> {
+        fs::create_dir_all(&self.dir)?;
+        let mut stmt = tx.prepare("SELECT base_type, url FROM base_types WHERE url IS NOT NULL ORDER BY base_type")?;
+        let base_types = stmt
+            .query_map([], |row| Ok((row.get::<usize, String>(0)?, row.get::<usize, String>(1)?)))?
+            .collect::<Result<Vec<_>, RusqliteError>>()?;
+
+        let mut report = ImageCacheReport::default();
+        for (base_type, url) in base_types {
+            let path = self.dir.join(paths::image_file_name(&base_type));
+            if path.exists() && fs::read(&path).is_ok_and(|bytes| self.validate(&base_type, &bytes).is_ok()) {
+                report.cached += 1;
+                continue;
+            }
+            match self.fetch(&base_type, &url, &path) {
+                Ok(()) => report.fetched += 1,
+                Err(e) => report.failed.push((base_type, e)),
+            }
+        }
+        Ok(report)
+    }
+
+    fn fetch(&self, base_type: &str, url: &str, path: &Path) -> Result<(), FgdbError> {
+        let url = Url::parse(url).map_err(|_| FgdbParseError::InvalidUrl(url.to_string()))?;
+        let bytes = self.fetcher.fetch(base_type, &url, self.max_image_size)?;
+        self.validate(base_type, &bytes)?;
+
+        // Write to a temporary file and then rename so that an interrupted write never leaves a partial image.
+        let temp_path = path.with_extension(consts::TEMP_FILE_EXTENSION);
+        fs::write(&temp_path, &bytes)?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    fn validate(&self, base_type: &str, bytes: &[u8]) -> Result<(), FgdbImageError> {
+        if bytes.len() as u64 > self.max_image_size {
+            return Err(FgdbImageError::TooLarge(base_type.to_string(), self.max_image_size));
+        }
+        let reader = Decoder::new(bytes)
+            .read_info()
+            .map_err(|e| FgdbImageError::InvalidPng(base_type.to_string(), e.to_string()))?;
+        let info = reader.info();
+        if info.width > self.max_image_dimension || info.height > self.max_image_dimension {
+            return Err(FgdbImageError::TooManyPixels(
+                base_type.to_string(),
+                info.width,
+                info.height,
+                self.max_image_dimension,
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use crate::db::conn::Conn;
+    use crate::image::dir_image_fetcher::DirImageFetcher;
+
+    fn create_cache(env_guard: &EnvPoolGuard, name: &str) -> ImageCache {
+        let fetcher = DirImageFetcher::new(Paths::new(env_guard.env).lookup(Dir::AssetsImagesTest));
+        ImageCache::create(env_guard.out_dir(name), Box::new(fetcher))
+    }
+
+    #[test]
+    fn test_populate_works() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let cache = create_cache(&env_guard, "test_populate_works");
+
+        // Only three base types have image fixtures; the remainder fail to fetch.
+        let report = cache.populate(&mut tx).unwrap();
+        assert_eq!(3, report.fetched);
+        assert_eq!(0, report.cached);
+        assert!(!report.failed.is_empty());
+
+        // A second populate finds the fetched images already cached.
+        let report = cache.populate(&mut tx).unwrap();
+        assert_eq!(0, report.fetched);
+        assert_eq!(3, report.cached);
+    }
+
+    #[test]
+    fn test_populate_replaces_corrupt_images() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let cache = create_cache(&env_guard, "test_populate_replaces_corrupt_images");
+        cache.populate(&mut tx).unwrap();
+
+        let corrupt_path = fs::read_dir(cache.dir()).unwrap().next().unwrap().unwrap().path();
+        fs::write(&corrupt_path, b"not a png").unwrap();
+        let report = cache.populate(&mut tx).unwrap();
+        assert_eq!(1, report.fetched);
+        assert_eq!(2, report.cached);
+    }
+
+    #[test]
+    fn test_populate_enforces_limits() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+
+        let cache = create_cache(&env_guard, "test_populate_enforces_size_limit").max_image_size(16);
+        let report = cache.populate(&mut tx).unwrap();
+        assert_eq!(0, report.fetched);
+        assert!(
+            report
+                .failed
+                .iter()
+                .any(|(_, e)| matches!(e, FgdbError::Image(FgdbImageError::TooLarge(..))))
+        );
+
+        let cache = create_cache(&env_guard, "test_populate_enforces_dimension_limit").max_image_dimension(2);
+        let report = cache.populate(&mut tx).unwrap();
+        assert_eq!(0, report.fetched);
+        assert!(
+            report
+                .failed
+                .iter()
+                .any(|(_, e)| matches!(e, FgdbError::Image(FgdbImageError::TooManyPixels(..))))
+        );
+    }
+
+    #[test]
+    fn test_collect_garbage_works() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let cache = create_cache(&env_guard, "test_collect_garbage_works");
+        cache.populate(&mut tx).unwrap();
+
+        let stale_path = cache.dir().join(paths::image_file_name("Removed Base Type"));
+        fs::write(&stale_path, b"stale").unwrap();
+        assert_eq!(1, cache.collect_garbage(&mut tx).unwrap());
+        assert!(!stale_path.exists());
+        assert_eq!(3, fs::read_dir(cache.dir()).unwrap().count());
+    }
+}
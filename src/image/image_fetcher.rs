@@ -0,0 +1,11 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::util::errors::FgdbError;
+use url::Url;
+
+pub trait ImageFetcher: Send + Sync {
+    /// Fetches the PNG for a base type.  Implementations must fail rather than return more than max_size bytes.
+    fn fetch(&self, base_type: &str, url: &Url, max_size: u64) -> Result<Vec<u8>, FgdbError>;
+}
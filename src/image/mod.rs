@@ -0,0 +1,10 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+pub mod color_extractor;
+pub mod color_histogram;
+pub mod dir_image_fetcher;
+pub mod http_image_fetcher;
+pub mod image_cache;
+pub mod image_fetcher;
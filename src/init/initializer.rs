@@ -2,18 +2,15 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 1/23/2026.
 
-#[cfg(test)]
-use crate::concurrency::env_pool::EnvPool;
-
+use crate::config::fgdb_config::try_get_config;
 use crate::db::conn::Conn;
-use crate::db::database::{Database, TABLES};
-use crate::fs::dir::Dir;
+use crate::db::database::Database;
 use crate::fs::paths;
-use crate::fs::paths::Paths;
 use crate::types::game_variant::GameVariant;
 use crate::util::env::Env;
-use std::fs::File;
-use std::io::BufReader;
+use crate::util::errors::FgdbError;
+use std::env;
+use std::io;
 use strum::IntoEnumIterator;
 
 pub struct Initializer;
@@ -25,61 +22,66 @@ impl Default for Initializer {
 }
 
 impl Initializer {
+    /// Initializes directories and databases.
+    ///
+    /// # Panics
+    ///
+    /// Panics if initialization fails.  Use try_new to handle initialization errors.
     pub fn new() -> Self {
-        Initializer::remove_env_out_dirs();
-        Initializer::create_dirs();
-        #[cfg(test)]
-        Initializer::init_env_pool();
-        Initializer::init_database();
-        Self {}
+        Initializer::try_new().unwrap_or_else(|e| panic!("Initialization failed: {}", e))
+    }
+
+    /// Initializes directories and databases, returning an error if the configuration cannot be loaded or if a
+    /// directory or database cannot be created.
+    pub fn try_new() -> Result<Self, FgdbError> {
+        try_get_config()?;
+        Initializer::remove_env_out_dirs()?;
+        Initializer::create_dirs()?;
+        Initializer::init_database()?;
+        Ok(Self {})
     }
 
-    fn create_dirs() {
+    fn create_dirs() -> Result<(), FgdbError> {
         for game_variant in GameVariant::iter() {
-            paths::create_cache_dirs(game_variant).unwrap();
-            for env in Env::iter() {
-                paths::create_env_dirs(game_variant, env).unwrap();
-                paths::create_env_out_dirs(game_variant, env).unwrap();
-            }
+            paths::create_cache_dirs(game_variant).map_err(|e| Initializer::describe(e, "create the cache directories"))?;
+            paths::create_env_dirs(game_variant, Env::Prod).map_err(|e| Initializer::describe(e, "create the environment directories"))?;
+            paths::create_env_out_dirs(game_variant, Env::Prod).map_err(|e| Initializer::describe(e, "create the output directories"))?;
         }
+        Ok(())
     }
 
-    #[cfg(test)]
-    fn init_env_pool() {
-        EnvPool::init();
+    // Adds the failed step and a suggested fix to IO errors, which otherwise lack the path involved.
+    fn describe(e: FgdbError, step: &str) -> FgdbError {
+        match e {
+            FgdbError::Io(e) => FgdbError::Io(io::Error::new(
+                e.kind(),
+                format!("Unable to {} in '{}': {}.  Check that the directory is writable.", step, Initializer::current_dir(), e),
+            )),
+            e => e,
+        }
     }
 
-    // Ensures that each table in the database exists, creating a new table if the table does not currently exist.  If
-    // an extant table is found, no changes to the table are made.
-    fn init_database() {
-        let db = Database::new().unwrap();
-        for game_variant in GameVariant::iter() {
-            for env in Env::iter() {
-                let mut conn = Conn::create(game_variant, env).unwrap();
-                let mut tx = conn.create_tx().unwrap();
-                db.create(&mut tx).unwrap();
+    fn current_dir() -> String {
+        env::current_dir().map(|d| d.display().to_string()).unwrap_or_default()
+    }
 
-                // For test environments, initialize each table with test data.
-                if Env::is_test_env(env) {
-                    for table in TABLES.iter() {
-                        table.delete(&mut tx).unwrap();
-                        let paths = Paths::new(Env::Prod);
-                        let src_path = paths.lookup(Dir::AssetsJsonTest).join(format!("{}.json", table.name()));
-                        let file = File::open(&src_path).unwrap();
-                        let mut reader = BufReader::new(file);
-                        table.import(&mut reader, &mut tx).unwrap();
-                    }
-                }
-            }
+    // Ensures that each table in the production database exists, creating a new table if the table does not currently
+    // exist.  No changes are made to extant tables.  Test environments are created on demand by EnvPool.
+    fn init_database() -> Result<(), FgdbError> {
+        let db = Database::new()?;
+        for game_variant in GameVariant::iter() {
+            let mut conn = Conn::create(game_variant, Env::Prod)?;
+            let mut tx = conn.create_tx()?;
+            db.create(&mut tx)?;
         }
+        Ok(())
     }
 
-    fn remove_env_out_dirs() {
+    fn remove_env_out_dirs() -> Result<(), FgdbError> {
         for game_variant in GameVariant::iter() {
-            for env in Env::iter() {
-                paths::remove_env_out_dirs(game_variant, env).unwrap();
-            }
+            paths::remove_env_out_dirs(game_variant, Env::Prod).map_err(|e| Initializer::describe(e, "remove the output directories"))?;
         }
+        Ok(())
     }
 }
 
@@ -88,8 +90,8 @@ mod tests {
     use super::*;
     use static_init::dynamic;
 
-    // The static initializer is used to ensure that the test databases are reset to an initial, known
-    // condition prior to running any tests.
+    // The static initializer is used to ensure that the production directories and database exist prior to running
+    // any tests.
     #[dynamic]
     static INITIALIZER: Initializer = Initializer::new();
 }
@@ -3,7 +3,7 @@
 // Created by Hankinsohl on 1/12/2026.
 
 #[cfg(any(feature = "std", feature = "errors"))]
-pub use util::errors::{FgdbDatFileError, FgdbFromSqlError, FgdbParseError, FgdbRangeError};
+pub use util::errors::{FgdbColumnError, FgdbDatFileError, FgdbError, FgdbFromSqlError, FgdbImageError, FgdbJsonError, FgdbParseError, FgdbRangeError, FgdbSoundPackError};
 
 #[cfg(any(feature = "std", feature = "names"))]
 pub use db::tables::names::*;
@@ -17,9 +17,9 @@ pub use db::rows::{
 
 #[cfg(any(feature = "std", feature = "types"))]
 pub use types::{
-    armor_type::ArmorType, font_size::FontSize, game_variant::GameVariant, gem_level::GemLevel, icon::Icon, icon_shape::IconShape, icon_size::IconSize,
-    item_level::ItemLevel, liquidity::Liquidity, non_unique_rarity::NonUniqueRarity, price::Price, rarity::Rarity, repository::Repository, sound::Sound,
-    sound_volume::SoundVolume, stack_size::StackSize, stock_color::StockColor, stock_sound::StockSound,
+    armor_type::ArmorType, font_size::FontSize, format_version::FormatVersion, game_variant::GameVariant, gem_level::GemLevel, icon::Icon, icon_shape::IconShape, icon_size::IconSize,
+    item_art::ItemArt, item_level::ItemLevel, liquidity::Liquidity, non_unique_rarity::NonUniqueRarity, price::Price, rarity::Rarity, repository::Repository,
+    sound::Sound, sound_volume::SoundVolume, stack_size::StackSize, stock_color::StockColor, stock_sound::StockSound,
 };
 
 #[cfg(feature = "std")]
@@ -29,6 +29,11 @@ pub use db::tables::{
     sounds_table::SoundsTable,
 };
 
+#[cfg(feature = "std")]
+pub use handle::fgdb::{Fgdb, FgdbBuilder};
+
+#[cfg(feature = "std")]
+pub mod color;
 #[cfg(feature = "std")]
 pub mod concurrency;
 #[cfg(feature = "std")]
@@ -36,8 +41,14 @@ pub mod config;
 #[cfg(any(feature = "std", feature = "names", feature = "rows"))]
 pub mod db;
 #[cfg(feature = "std")]
+pub mod export;
+#[cfg(feature = "std")]
 pub mod fs;
 #[cfg(feature = "std")]
+pub mod handle;
+#[cfg(feature = "std")]
+pub mod image;
+#[cfg(feature = "std")]
 pub mod init;
 #[cfg(feature = "std")]
 pub mod net;
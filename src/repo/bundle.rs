@@ -0,0 +1,282 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::concurrency::cancellation_token::CancellationToken;
+use crate::db::database::TABLES;
+use crate::util::consts;
+use crate::util::errors::FgdbError;
+use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+/// Limits applied when a bundle is extracted.  A bundle which exceeds any limit is rejected as a possible zip bomb.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BundleLimits {
+    pub max_entries: usize,
+
+    // The maximum uncompressed size in bytes of a single entry.
+    pub max_entry_size: u64,
+
+    // The maximum uncompressed size in bytes of all entries together.
+    pub max_total_size: u64,
+
+    // The maximum ratio of the uncompressed to the compressed size of an entry.
+    pub max_compression_ratio: u64,
+}
+
+impl Default for BundleLimits {
+    fn default() -> Self {
+        Self {
+            max_entries: consts::BUNDLE_MAX_ENTRIES,
+            max_entry_size: consts::BUNDLE_MAX_ENTRY_SIZE,
+            max_total_size: consts::BUNDLE_MAX_TOTAL_SIZE,
+            max_compression_ratio: consts::BUNDLE_MAX_COMPRESSION_RATIO,
+        }
+    }
+}
+
+/// Extracts the bundle at zip_path into dst_dir.  The bundle may hold only the JSON file of each table and the
+/// manifest, each at the top level; entries with any other name, directories, symbolic links and entries exceeding
+/// limits are rejected with FgdbError::Repository.  The bundle is extracted into a staging directory next to dst_dir
+/// which replaces dst_dir only once every table has been extracted, so a rejected, failed or cancelled extraction
+/// leaves dst_dir unchanged.
+pub fn extract_bundle(zip_path: &Path, dst_dir: &Path, limits: &BundleLimits, token: &CancellationToken) -> Result<(), FgdbError> {
+    let staging_dir = dst_dir.with_extension(consts::STAGING_DIR_EXTENSION);
+    if fs::exists(&staging_dir)? {
+        fs::remove_dir_all(&staging_dir)?;
+    }
+    fs::create_dir_all(&staging_dir)?;
+    if let Err(e) = extract_entries(zip_path, &staging_dir, limits, token) {
+        let _ = fs::remove_dir_all(&staging_dir);
+        return Err(e);
+    }
+
+    // Swap the staging directory in.  The old directory is moved aside rather than removed first so that dst_dir is
+    // restored if the staging directory cannot be renamed.
+    let retired_dir = dst_dir.with_extension(consts::RETIRED_DIR_EXTENSION);
+    if fs::exists(&retired_dir)? {
+        fs::remove_dir_all(&retired_dir)?;
+    }
+    let has_old = fs::exists(dst_dir)?;
+    if has_old {
+        fs::rename(dst_dir, &retired_dir)?;
+    }
+    if let Err(e) = fs::rename(&staging_dir, dst_dir) {
+        if has_old {
+            let _ = fs::rename(&retired_dir, dst_dir);
+        }
+        return Err(e.into());
+    }
+    if has_old {
+        fs::remove_dir_all(&retired_dir)?;
+    }
+    Ok(())
+}
+
+// Extracts and checks every entry of the bundle at zip_path into dir.
+fn extract_entries(zip_path: &Path, dir: &Path, limits: &BundleLimits, token: &CancellationToken) -> Result<(), FgdbError> {
+    let mut archive = ZipArchive::new(BufReader::new(File::open(zip_path)?)).map_err(|e| to_fgdb_error(zip_path, e))?;
+    if archive.len() > limits.max_entries {
+        return Err(reject(
+            zip_path,
+            &format!("it has {} entries; the limit is {}", archive.len(), limits.max_entries),
+        ));
+    }
+
+    let table_names: Vec<String> = TABLES.iter().map(|table| format!("{}.json", table.name())).collect();
+    let mut extracted = HashSet::new();
+    let mut total_size = 0u64;
+    for index in 0..archive.len() {
+        token.check()?;
+        let mut entry = archive.by_index(index).map_err(|e| to_fgdb_error(zip_path, e))?;
+        let name = entry.name().to_string();
+
+        // enclosed_name rejects absolute paths and paths which escape the extraction directory.
+        if entry.enclosed_name().is_none() {
+            return Err(reject(zip_path, &format!("entry '{}' escapes the extraction directory", name)));
+        }
+        if entry.is_symlink() {
+            return Err(reject(zip_path, &format!("entry '{}' is a symbolic link", name)));
+        }
+        if entry.is_dir() || (name != consts::MANIFEST_FILE_NAME && !table_names.contains(&name)) {
+            return Err(reject(zip_path, &format!("entry '{}' is not a table or the manifest", name)));
+        }
+        if !extracted.insert(name.clone()) {
+            return Err(reject(zip_path, &format!("entry '{}' appears more than once", name)));
+        }
+        if entry.size() > limits.max_entry_size {
+            return Err(reject(
+                zip_path,
+                &format!("entry '{}' is {} bytes; the limit is {}", name, entry.size(), limits.max_entry_size),
+            ));
+        }
+        if entry.size() > entry.compressed_size().max(1).saturating_mul(limits.max_compression_ratio) {
+            return Err(reject(
+                zip_path,
+                &format!("entry '{}' exceeds the compression ratio limit of {}", name, limits.max_compression_ratio),
+            ));
+        }
+
+        // The sizes recorded in the bundle may be forged, so the bytes actually written are limited as well.
+        let limit = limits.max_entry_size.min(limits.max_total_size - total_size);
+        let mut writer = File::create(dir.join(&name))?;
+        let written = io::copy(&mut (&mut entry).take(limit.saturating_add(1)), &mut writer).map_err(|e| to_fgdb_error(zip_path, ZipError::Io(e)))?;
+        if written > limit {
+            return Err(reject(zip_path, &format!("entry '{}' exceeds the size limit", name)));
+        }
+        total_size += written;
+    }
+
+    if let Some(missing) = table_names.iter().find(|name| !extracted.contains(*name)) {
+        return Err(reject(zip_path, &format!("it does not contain '{}'", missing)));
+    }
+    Ok(())
+}
+
+fn reject(zip_path: &Path, reason: &str) -> FgdbError {
+    FgdbError::Repository(format!("Bundle '{}' was rejected because {}.", zip_path.display(), reason), None)
+}
+
+fn to_fgdb_error(zip_path: &Path, e: ZipError) -> FgdbError {
+    match e {
+        ZipError::Io(e) if e.kind() != io::ErrorKind::InvalidData => FgdbError::Io(e),
+        e => FgdbError::Repository(format!("Bundle '{}' is invalid: {}.", zip_path.display(), e), Some(Box::new(e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    // Returns the JSON file name of every table.
+    fn table_files() -> Vec<String> {
+        TABLES.iter().map(|table| format!("{}.json", table.name())).collect()
+    }
+
+    // Writes a bundle holding entries to zip_path.  Entries whose contents are None are written as symbolic links.
+    fn write_bundle(zip_path: &Path, entries: &[(String, Option<Vec<u8>>)]) {
+        let mut writer = ZipWriter::new(File::create(zip_path).unwrap());
+        for (name, contents) in entries {
+            match contents {
+                Some(contents) => {
+                    writer.start_file(name, SimpleFileOptions::default()).unwrap();
+                    writer.write_all(contents).unwrap();
+                }
+                None => writer.add_symlink(name, "/etc/passwd", SimpleFileOptions::default()).unwrap(),
+            }
+        }
+        writer.finish().unwrap();
+    }
+
+    // Returns a valid bundle's entries together with extra.
+    fn entries_with(extra: (&str, Option<Vec<u8>>)) -> Vec<(String, Option<Vec<u8>>)> {
+        let mut entries: Vec<(String, Option<Vec<u8>>)> = table_files().into_iter().map(|name| (name, Some(b"[]".to_vec()))).collect();
+        entries.push((extra.0.to_string(), extra.1));
+        entries
+    }
+
+    // Creates the directory for a test, returning the bundle path and the destination directory, which holds a single
+    // file named stale.json.
+    fn setup(test_name: &str, env_guard: &EnvPoolGuard) -> (PathBuf, PathBuf) {
+        let dir = env_guard.out_dir(test_name);
+        let dst_dir = dir.join("json");
+        fs::create_dir_all(&dst_dir).unwrap();
+        fs::write(dst_dir.join("stale.json"), "[]").unwrap();
+        (dir.join(consts::REPOSITORY_ZIP_FILE_NAME), dst_dir)
+    }
+
+    #[test]
+    fn test_extract_bundle_replaces_destination() {
+        let env_guard = EnvPoolGuard::new();
+        let (zip_path, dst_dir) = setup("test_extract_bundle_replaces_destination", &env_guard);
+        write_bundle(&zip_path, &entries_with((consts::MANIFEST_FILE_NAME, Some(b"{}".to_vec()))));
+
+        extract_bundle(&zip_path, &dst_dir, &BundleLimits::default(), &CancellationToken::new()).unwrap();
+        assert!(!dst_dir.join("stale.json").exists());
+        assert!(table_files().iter().all(|name| dst_dir.join(name).exists()));
+        assert!(dst_dir.join(consts::MANIFEST_FILE_NAME).exists());
+        assert!(!dst_dir.with_extension(consts::STAGING_DIR_EXTENSION).exists());
+        assert!(!dst_dir.with_extension(consts::RETIRED_DIR_EXTENSION).exists());
+    }
+
+    #[test]
+    fn test_extract_bundle_rejects_unsafe_entries() {
+        let env_guard = EnvPoolGuard::new();
+        let (zip_path, dst_dir) = setup("test_extract_bundle_rejects_unsafe_entries", &env_guard);
+        let cases = [
+            (("../classes.json", Some(b"[]".to_vec())), "escapes the extraction directory"),
+            (("/etc/classes.json", Some(b"[]".to_vec())), "escapes the extraction directory"),
+            (("link.json", None), "is a symbolic link"),
+            (("readme.txt", Some(b"hello".to_vec())), "is not a table or the manifest"),
+            (("sub/classes.json", Some(b"[]".to_vec())), "is not a table or the manifest"),
+            ((consts::MANIFEST_FILE_NAME, Some(vec![b' '; 100_000])), "compression ratio"),
+        ];
+        for (extra, reason) in cases {
+            write_bundle(&zip_path, &entries_with(extra));
+            let result = extract_bundle(&zip_path, &dst_dir, &BundleLimits::default(), &CancellationToken::new());
+            assert!(
+                matches!(result, Err(FgdbError::Repository(message, _)) if message.contains(reason)),
+                "{}",
+                reason
+            );
+
+            // The destination is left unchanged.
+            assert!(dst_dir.join("stale.json").exists());
+            assert!(!dst_dir.with_extension(consts::STAGING_DIR_EXTENSION).exists());
+        }
+    }
+
+    #[test]
+    fn test_extract_bundle_enforces_limits() {
+        let env_guard = EnvPoolGuard::new();
+        let (zip_path, dst_dir) = setup("test_extract_bundle_enforces_limits", &env_guard);
+        write_bundle(
+            &zip_path,
+            &entries_with((consts::MANIFEST_FILE_NAME, Some(b"{\"format_version\": 1}".to_vec()))),
+        );
+        let extract = |limits: BundleLimits| extract_bundle(&zip_path, &dst_dir, &limits, &CancellationToken::new());
+
+        let result = extract(BundleLimits {
+            max_entries: 3,
+            ..BundleLimits::default()
+        });
+        assert!(matches!(result, Err(FgdbError::Repository(message, _)) if message.contains("entries; the limit is 3")));
+        let result = extract(BundleLimits {
+            max_entry_size: 10,
+            ..BundleLimits::default()
+        });
+        assert!(matches!(result, Err(FgdbError::Repository(message, _)) if message.contains("the limit is 10")));
+        let result = extract(BundleLimits {
+            max_total_size: 10,
+            ..BundleLimits::default()
+        });
+        assert!(matches!(result, Err(FgdbError::Repository(message, _)) if message.contains("exceeds the size limit")));
+        assert!(dst_dir.join("stale.json").exists());
+
+        // A bundle lacking a table is rejected.
+        let mut entries = entries_with((consts::MANIFEST_FILE_NAME, Some(b"{}".to_vec())));
+        entries.remove(0);
+        write_bundle(&zip_path, &entries);
+        let result = extract(BundleLimits::default());
+        assert!(matches!(result, Err(FgdbError::Repository(message, _)) if message.contains("does not contain")));
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(matches!(
+            extract_bundle(&zip_path, &dst_dir, &BundleLimits::default(), &token),
+            Err(FgdbError::Cancelled)
+        ));
+        assert!(dst_dir.join("stale.json").exists());
+    }
+}
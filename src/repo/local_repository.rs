@@ -2,18 +2,31 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 2/25/2026.
 
+#[cfg(feature = "async")]
+use crate::concurrency::blocking_task::{spawn_blocking, BlockingTask};
+use crate::concurrency::cancellation_token::CancellationToken;
 use crate::config::fgdb_config::get_config;
 use crate::fs::dir::Dir;
 use crate::fs::paths::Paths;
 use crate::repo::repository::Repository;
+use crate::types::repository::Repository as RepositoryType;
+use crate::update::progress::{NoProgress, Progress, ProgressObserver};
 use crate::util::consts;
 use crate::util::env::Env;
 use crate::GameVariant;
-use anyhow::Error;
+use crate::util::errors::FgdbError;
 use slitu::Timestamp;
 use std::fs;
-use std::path::PathBuf;
+use std::fs::{File, Metadata, OpenOptions};
+use std::io;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
 
+// Size of the chunks in which the repository zip file is copied.
+const COPY_CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Clone, Debug)]
 pub struct LocalRepository {
     paths: Paths,
     repository_path: PathBuf,
@@ -27,32 +40,78 @@ impl Default for LocalRepository {
 }
 
 impl Repository for LocalRepository {
-    fn download(&self) -> Result<(), Error> {
-        let cache_path = PathBuf::from(self.paths.lookup(Dir::CacheZip)).join(consts::REPOSITORY_ZIP_FILE_NAME);
+    fn download(&self) -> Result<(), FgdbError> {
+        self.download_with_progress(&NoProgress, &CancellationToken::new())
+    }
+
+    fn download_with_progress(&self, observer: &dyn ProgressObserver, token: &CancellationToken) -> Result<(), FgdbError> {
+        let cache_path = self.paths.lookup(Dir::CacheZip).join(consts::REPOSITORY_ZIP_FILE_NAME);
 
         // Copy the zip file in the repository to the cache zip directory.
-        fs::copy(&self.zip_path, &cache_path)?;
+        copy_cancellable(&self.zip_path, &cache_path, token, &mut |bytes, total| {
+            observer.on_progress(&Progress::Downloading { bytes, total })
+        })?;
 
-        // Copy the repository timestamp to the cache timestamp directory.
+        // Copy the signature file of the zip file, if it is signed, so the zip file can be verified before extraction.
+        let signature_path = self.zip_path.with_file_name(consts::REPOSITORY_SIGNATURE_FILE_NAME);
+        let cache_signature_path = self.paths.lookup(Dir::CacheZip).join(consts::REPOSITORY_SIGNATURE_FILE_NAME);
+        if fs::exists(&signature_path).map_err(|e| read_error(&signature_path, e))? {
+            fs::write(&cache_signature_path, fs::read(&signature_path).map_err(|e| read_error(&signature_path, e))?)?;
+        } else if fs::exists(&cache_signature_path)? {
+            fs::remove_file(&cache_signature_path)?;
+        }
+
+        // Copy the repository timestamp to the cache timestamp directory, pending the import of the zip file.
         let repository_timestamp_path = self.repository_path.join(consts::REPOSITORY_TIMESTAMP_DIR).join(consts::TIMESTAMP_FILE_NAME);
-        let cache_timestamp_path = self.paths.lookup(Dir::CacheTimestamp).join(consts::TIMESTAMP_FILE_NAME);
-        fs::copy(&repository_timestamp_path, &cache_timestamp_path)?;
+        let repository_timestamp = fs::read(&repository_timestamp_path).map_err(|e| read_error(&repository_timestamp_path, e))?;
+        fs::write(self.pending_timestamp_path(), repository_timestamp)?;
         Ok(())
     }
 
-    fn is_cache_current(&self) -> Result<bool, Error> {
+    #[cfg(feature = "async")]
+    fn download_async(&self, token: CancellationToken) -> BlockingTask<Result<(), FgdbError>> {
+        let repository = self.clone();
+        spawn_blocking(move || repository.download_with_progress(&NoProgress, &token))
+    }
+
+    /// Compares the timestamp in the cache/timestamp directory against the timestamp in the repository.  If the
+    /// timestamp file is missing from the cache/timestamp directory, false is returned.
+    fn is_cache_current(&self) -> Result<bool, FgdbError> {
         let repository_timestamp_path = self.repository_path.join(consts::REPOSITORY_TIMESTAMP_DIR).join(consts::TIMESTAMP_FILE_NAME);
-        let repository_timestamp = Timestamp::from_path(&repository_timestamp_path)?;
+        // Fails if the repository cannot be reached, e.g., because its drive is not mounted.
+        fs::metadata(&repository_timestamp_path).map_err(|e| read_error(&repository_timestamp_path, e))?;
+        let repository_timestamp = Timestamp::from_path(&repository_timestamp_path).map_err(|e| FgdbError::Repository(e.to_string(), Some(e.into())))?;
 
         let cache_timestamp_path = self.paths.lookup(Dir::CacheTimestamp).join(consts::TIMESTAMP_FILE_NAME);
         let is_current = if fs::exists(&cache_timestamp_path)? {
-            let cache_timestamp = Timestamp::from_path(&cache_timestamp_path)?;
+            let cache_timestamp = Timestamp::from_path(&cache_timestamp_path).map_err(|e| FgdbError::Repository(e.to_string(), Some(e.into())))?;
             cache_timestamp.is_current(&repository_timestamp)
         } else {
             false
         };
         Ok(is_current)
     }
+
+    /// Returns the contents of the timestamp file in the cache/timestamp directory.
+    fn cached_version(&self) -> Option<String> {
+        fs::read_to_string(self.paths.lookup(Dir::CacheTimestamp).join(consts::TIMESTAMP_FILE_NAME)).ok()
+    }
+
+    fn mark_cache_current(&self) -> Result<(), FgdbError> {
+        let pending_timestamp_path = self.pending_timestamp_path();
+        if fs::exists(&pending_timestamp_path)? {
+            fs::rename(&pending_timestamp_path, self.paths.lookup(Dir::CacheTimestamp).join(consts::TIMESTAMP_FILE_NAME))?;
+        }
+        Ok(())
+    }
+
+    fn location(&self) -> String {
+        self.repository_path.display().to_string()
+    }
+
+    fn repository_type(&self) -> RepositoryType {
+        RepositoryType::Local
+    }
 }
 
 impl LocalRepository {
@@ -61,8 +120,12 @@ impl LocalRepository {
     }
 
     pub fn create(root_path: PathBuf, game_variant: GameVariant) -> Self {
-        let paths = Paths::create(game_variant, Env::Prod);
-        let repository_path = root_path.join(game_variant.to_string());
+        LocalRepository::with_paths(root_path, Paths::create(game_variant, Env::Prod))
+    }
+
+    /// Creates a repository which downloads into the cache directories of paths.
+    pub fn with_paths(root_path: PathBuf, paths: Paths) -> Self {
+        let repository_path = root_path.join(paths.game_variant().to_string());
         let zip_path = repository_path.join(consts::REPOSITORY_ZIP_DIR).join(consts::REPOSITORY_ZIP_FILE_NAME);
         Self {
             paths,
@@ -74,11 +137,172 @@ impl LocalRepository {
     pub fn get_zip_path(&self) -> PathBuf {
         self.zip_path.clone()
     }
+
+    fn pending_timestamp_path(&self) -> PathBuf {
+        self.paths.lookup(Dir::CacheTimestamp).join(consts::TIMESTAMP_FILE_NAME).with_extension(consts::PENDING_FILE_EXTENSION)
+    }
+}
+
+// Copies src to dst in chunks, checking token between chunks and calling progress with the number of bytes copied so
+// far and the size of src after each chunk.  The copy is written to a temporary file which is renamed once complete, so
+// a cancelled or failed copy never leaves a partial file at dst.  The size and modification time of src are recorded
+// beside the temporary file, and a partial copy left by an earlier attempt is resumed only if src still matches them.
+fn copy_cancellable(src: &Path, dst: &Path, token: &CancellationToken, progress: &mut dyn FnMut(u64, u64)) -> Result<(), FgdbError> {
+    let temp_path = dst.with_extension(consts::TEMP_FILE_EXTENSION);
+    let source_path = dst.with_extension(consts::PARTIAL_SOURCE_FILE_EXTENSION);
+    let mut reader = File::open(src).map_err(|e| read_error(src, e))?;
+    let src_metadata = reader.metadata().map_err(|e| read_error(src, e))?;
+    let total = src_metadata.len();
+    let identity = source_identity(&src_metadata);
+    let bytes = match fs::metadata(&temp_path) {
+        Ok(temp_metadata) if temp_metadata.len() <= total && identity.is_some() && fs::read_to_string(&source_path).ok() == identity => {
+            temp_metadata.len()
+        }
+        _ => 0,
+    };
+    let mut writer = if bytes > 0 {
+        reader.seek(SeekFrom::Start(bytes)).map_err(|e| read_error(src, e))?;
+        OpenOptions::new().append(true).open(&temp_path)?
+    } else {
+        // The temporary file is truncated before the identity of src is recorded so that the two always agree.
+        let writer = File::create(&temp_path)?;
+        match &identity {
+            Some(identity) => fs::write(&source_path, identity)?,
+            None if fs::exists(&source_path)? => fs::remove_file(&source_path)?,
+            None => {}
+        }
+        writer
+    };
+
+    copy_chunks(&mut reader, &mut writer, bytes, total, token, progress, &|e| read_error(src, e))?;
+    drop(writer);
+    fs::rename(&temp_path, dst)?;
+    if fs::exists(&source_path)? {
+        fs::remove_file(&source_path)?;
+    }
+    Ok(())
+}
+
+// Copies reader to writer in chunks, checking token between chunks.  bytes is the number of bytes already copied and
+// total the expected size, which are passed to progress before the first chunk and after each chunk.  Errors reading
+// reader are converted by on_read_error so that they can be told apart from errors writing writer.
+pub(crate) fn copy_chunks(
+    reader: &mut dyn Read,
+    writer: &mut dyn Write,
+    mut bytes: u64,
+    total: u64,
+    token: &CancellationToken,
+    progress: &mut dyn FnMut(u64, u64),
+    on_read_error: &dyn Fn(io::Error) -> FgdbError,
+) -> Result<(), FgdbError> {
+    let mut buffer = vec![0u8; COPY_CHUNK_SIZE];
+    progress(bytes, total);
+    loop {
+        token.check()?;
+        let count = reader.read(&mut buffer).map_err(on_read_error)?;
+        if count == 0 {
+            break;
+        }
+        writer.write_all(&buffer[..count])?;
+        bytes += count as u64;
+        progress(bytes, total);
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+// Reports an error reading path in the repository as the repository being unreachable rather than as a local I/O error.
+fn read_error(path: &Path, e: io::Error) -> FgdbError {
+    FgdbError::Unreachable(format!("'{}' could not be read: {}.", path.display(), e), e)
+}
+
+// Returns the size and modification time of the file described by metadata, or None if the modification time is not
+// available.
+fn source_identity(metadata: &Metadata) -> Option<String> {
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some(format!("{} {}", metadata.len(), modified.as_nanos()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+
+    #[test]
+    fn test_copy_cancellable_leaves_no_partial_file() {
+        let env_guard = EnvPoolGuard::new();
+        let dir = Paths::new(env_guard.env).lookup(Dir::EnvOut);
+        let src = dir.join("src.zip");
+        let dst = dir.join("dst.zip");
+        fs::write(&src, vec![7u8; COPY_CHUNK_SIZE * 2 + 1]).unwrap();
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(matches!(copy_cancellable(&src, &dst, &token, &mut |_, _| {}), Err(FgdbError::Cancelled)));
+        assert!(!dst.exists());
+
+        let mut reports = Vec::new();
+        copy_cancellable(&src, &dst, &CancellationToken::new(), &mut |bytes, total| reports.push((bytes, total))).unwrap();
+        assert_eq!(fs::read(&src).unwrap(), fs::read(&dst).unwrap());
+        let total = (COPY_CHUNK_SIZE * 2 + 1) as u64;
+        assert_eq!(Some(&(0, total)), reports.first());
+        assert_eq!(Some(&(total, total)), reports.last());
+        assert!(reports.windows(2).all(|w| w[0].0 <= w[1].0));
+        assert!(!dst.with_extension(consts::TEMP_FILE_EXTENSION).exists());
+    }
+
+    #[test]
+    fn test_copy_cancellable_resumes_partial_copy() {
+        let env_guard = EnvPoolGuard::new();
+        let dir = Paths::new(env_guard.env).lookup(Dir::EnvOut);
+        let src = dir.join("src.zip");
+        let dst = dir.join("dst.zip");
+        let contents = (0..COPY_CHUNK_SIZE * 2 + 1).map(|i| i as u8).collect::<Vec<_>>();
+        fs::write(&src, &contents).unwrap();
+        let write_partial_copy = || {
+            fs::write(dst.with_extension(consts::TEMP_FILE_EXTENSION), &contents[..COPY_CHUNK_SIZE]).unwrap();
+            let identity = source_identity(&fs::metadata(&src).unwrap()).unwrap();
+            fs::write(dst.with_extension(consts::PARTIAL_SOURCE_FILE_EXTENSION), identity).unwrap();
+        };
+
+        write_partial_copy();
+        let mut reports = Vec::new();
+        copy_cancellable(&src, &dst, &CancellationToken::new(), &mut |bytes, _| reports.push(bytes)).unwrap();
+        assert_eq!(Some(&(COPY_CHUNK_SIZE as u64)), reports.first());
+        assert_eq!(contents, fs::read(&dst).unwrap());
+        assert!(!dst.with_extension(consts::PARTIAL_SOURCE_FILE_EXTENSION).exists());
+
+        // A partial copy of a source which has since been replaced is discarded, even if the replacement is older.
+        write_partial_copy();
+        let replacement = vec![9u8; contents.len()];
+        fs::write(&src, &replacement).unwrap();
+        File::options().write(true).open(&src).unwrap().set_modified(UNIX_EPOCH).unwrap();
+        let mut reports = Vec::new();
+        copy_cancellable(&src, &dst, &CancellationToken::new(), &mut |bytes, _| reports.push(bytes)).unwrap();
+        assert_eq!(Some(&0), reports.first());
+        assert_eq!(replacement, fs::read(&dst).unwrap());
+    }
+
+    #[test]
+    fn test_only_repository_read_errors_are_unreachable() {
+        let env_guard = EnvPoolGuard::new();
+        let dir = Paths::new(env_guard.env).lookup(Dir::EnvOut).join("test_only_repository_read_errors_are_unreachable");
+        let game_variant = Paths::new(env_guard.env).game_variant();
+        let cache_paths = Paths::with_root(&dir.join("missing"), game_variant, Env::Prod);
+
+        let repo = LocalRepository::with_paths(dir.join("missing"), cache_paths.clone());
+        assert!(repo.is_cache_current().unwrap_err().is_unreachable());
+        assert!(repo.download().unwrap_err().is_unreachable());
+
+        // The repository is readable but the cache directory does not exist, so writing the cache fails locally.
+        let root = dir.join("repository");
+        let zip_dir = root.join(game_variant.to_string()).join(consts::REPOSITORY_ZIP_DIR);
+        fs::create_dir_all(&zip_dir).unwrap();
+        fs::write(zip_dir.join(consts::REPOSITORY_ZIP_FILE_NAME), b"bundle").unwrap();
+        let result = LocalRepository::with_paths(root, cache_paths).download();
+        assert!(matches!(&result, Err(FgdbError::Io(_))));
+        assert!(!result.unwrap_err().is_unreachable());
+    }
 
     #[test]
     fn test_download() {
@@ -0,0 +1,65 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::types::format_version::FormatVersion;
+use crate::util::consts;
+use crate::util::errors::FgdbError;
+use crate::util::json;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// Describes the JSON files in a repository.  The manifest is stored alongside the JSON files as manifest.json.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Manifest {
+    // The format of the JSON files.
+    pub format_version: FormatVersion,
+}
+
+impl Manifest {
+    pub fn new(format_version: FormatVersion) -> Self {
+        Self { format_version }
+    }
+
+    /// Reads the manifest from dir.  Repositories created before the manifest was introduced lack one; for these the
+    /// default manifest, which specifies the nested format, is returned.
+    pub fn read(dir: &Path) -> Result<Self, FgdbError> {
+        let path = dir.join(consts::MANIFEST_FILE_NAME);
+        if !fs::exists(&path)? {
+            return Ok(Manifest::default());
+        }
+        let reader = BufReader::new(File::open(&path)?);
+        Ok(json::from_reader(reader)?)
+    }
+
+    /// Writes the manifest to dir.
+    pub fn write(&self, dir: &Path) -> Result<(), FgdbError> {
+        fs::write(dir.join(consts::MANIFEST_FILE_NAME), json::to_string(self, FormatVersion::Nested)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+
+    #[test]
+    fn test_read_and_write_work() {
+        let env_guard = EnvPoolGuard::new();
+        let dir = env_guard.out_dir("test_read_and_write_work");
+
+        // A missing manifest yields the nested format.
+        assert_eq!(FormatVersion::Nested, Manifest::read(&dir).unwrap().format_version);
+
+        Manifest::new(FormatVersion::Flat).write(&dir).unwrap();
+        assert_eq!("{\n  \"format_version\": 2\n}", fs::read_to_string(dir.join(consts::MANIFEST_FILE_NAME)).unwrap());
+        assert_eq!(FormatVersion::Flat, Manifest::read(&dir).unwrap().format_version);
+
+        fs::write(dir.join(consts::MANIFEST_FILE_NAME), "{\"format_version\": 99}").unwrap();
+        assert!(Manifest::read(&dir).is_err());
+    }
+}
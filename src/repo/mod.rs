@@ -2,6 +2,10 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 2/25/2026.
 
+pub mod bundle;
 pub mod local_repository;
+pub mod manifest;
 pub mod remote_repository;
 pub mod repository;
+pub mod retry_policy;
+pub mod signature;
@@ -1,3 +1,455 @@
 // Copyright (c) 2026 By David "Hankinsohl" Hankins.
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 2/25/2026.
+
+#[cfg(feature = "async")]
+use crate::concurrency::blocking_task::{spawn_blocking, BlockingTask};
+use crate::concurrency::cancellation_token::CancellationToken;
+use crate::fs::dir::Dir;
+use crate::fs::paths::Paths;
+use crate::repo::local_repository::copy_chunks;
+use crate::repo::repository::Repository;
+use crate::types::format_version::FormatVersion;
+use crate::types::repository::Repository as RepositoryType;
+use crate::update::progress::{NoProgress, Progress, ProgressObserver};
+use crate::util::consts;
+use crate::util::errors::{FgdbError, FgdbParseError};
+use crate::util::http;
+use crate::util::json;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use ureq::http::header::{HeaderMap, CONTENT_RANGE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, IF_RANGE, LAST_MODIFIED, RANGE};
+use ureq::http::{Response, StatusCode};
+use ureq::{Agent, Body};
+use url::Url;
+
+// The HTTP validators of the cached bundle.  These are sent with freshness checks so an unchanged bundle costs a single
+// 304 response.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl Validators {
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name| headers.get(name).and_then(|value| value.to_str().ok()).map(str::to_string);
+        Self {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    // Reads the validators from path, returning None if the file does not exist.
+    fn read(path: &Path) -> Result<Option<Self>, FgdbError> {
+        if !fs::exists(path)? {
+            return Ok(None);
+        }
+        Ok(Some(json::from_reader(BufReader::new(File::open(path)?))?))
+    }
+
+    fn write(&self, path: &Path) -> Result<(), FgdbError> {
+        fs::write(path, json::to_string(self, FormatVersion::Nested)?)?;
+        Ok(())
+    }
+}
+
+/// A repository served over HTTP.  The bundle for a game variant is fetched from <url>/<game variant>/zip/poedb.zip and
+/// its signature file, if it is signed, from poedb.zip.sig alongside it.  Freshness is checked with a conditional HEAD
+/// request carrying the ETag and Last-Modified validators of the cached bundle, which are kept in the cache/timestamp
+/// directory.
+#[derive(Clone, Debug)]
+pub struct RemoteRepository {
+    agent: Agent,
+    paths: Paths,
+    bundle_url: Url,
+    signature_url: Url,
+}
+
+impl Repository for RemoteRepository {
+    fn download(&self) -> Result<(), FgdbError> {
+        self.download_with_progress(&NoProgress, &CancellationToken::new())
+    }
+
+    /// Downloads the bundle into a temporary file which is renamed once complete, so a failed download never leaves a
+    /// partial bundle in the cache.  The validators of the bundle are kept beside the temporary file, and a download
+    /// which fails part way is resumed by the next attempt with a range request if the bundle is unchanged.
+    fn download_with_progress(&self, observer: &dyn ProgressObserver, token: &CancellationToken) -> Result<(), FgdbError> {
+        token.check()?;
+        let cache_path = self.paths.lookup(Dir::CacheZip).join(consts::REPOSITORY_ZIP_FILE_NAME);
+        let temp_path = cache_path.with_extension(consts::TEMP_FILE_EXTENSION);
+        let source_path = cache_path.with_extension(consts::PARTIAL_SOURCE_FILE_EXTENSION);
+        let (mut response, bytes, validators) = self.request_bundle(&temp_path, &source_path)?;
+        let mut writer = if bytes > 0 {
+            OpenOptions::new().append(true).open(&temp_path)?
+        } else {
+            // The temporary file is truncated before its validators are replaced so that the two always agree.
+            let writer = File::create(&temp_path)?;
+            validators.write(&source_path)?;
+            writer
+        };
+
+        let total = bytes + response.body().content_length().unwrap_or(0);
+        let mut progress = |bytes, total| observer.on_progress(&Progress::Downloading { bytes, total });
+        copy_chunks(&mut response.body_mut().as_reader(), &mut writer, bytes, total, token, &mut progress, &|e| self.unreachable(e))?;
+        drop(writer);
+        fs::rename(&temp_path, &cache_path)?;
+        fs::remove_file(&source_path)?;
+        self.download_signature()?;
+        validators.write(&self.validators_path().with_extension(consts::PENDING_FILE_EXTENSION))
+    }
+
+    #[cfg(feature = "async")]
+    fn download_async(&self, token: CancellationToken) -> BlockingTask<Result<(), FgdbError>> {
+        let repository = self.clone();
+        spawn_blocking(move || repository.download_with_progress(&NoProgress, &token))
+    }
+
+    /// Sends a conditional HEAD request for the bundle.  The cache is current if the server answers 304 Not Modified
+    /// or, for servers which ignore conditional HEAD requests, if the validators returned match those of the cache.
+    fn is_cache_current(&self) -> Result<bool, FgdbError> {
+        let cache_path = self.paths.lookup(Dir::CacheZip).join(consts::REPOSITORY_ZIP_FILE_NAME);
+        let validators = match Validators::read(&self.validators_path())? {
+            Some(validators) if !validators.is_empty() && fs::exists(&cache_path)? => validators,
+            _ => return Ok(false),
+        };
+
+        let mut request = self.agent.head(self.bundle_url.as_str());
+        if let Some(etag) = &validators.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        let response = request.call().map_err(|e| self.to_fgdb_error(e))?;
+        Ok(response.status() == StatusCode::NOT_MODIFIED || Validators::from_headers(response.headers()) == validators)
+    }
+
+    /// Returns the ETag of the cached bundle or, if the server sent none, its Last-Modified date.
+    fn cached_version(&self) -> Option<String> {
+        let validators = Validators::read(&self.validators_path()).ok()??;
+        validators.etag.or(validators.last_modified)
+    }
+
+    fn mark_cache_current(&self) -> Result<(), FgdbError> {
+        let validators_path = self.validators_path();
+        let pending_validators_path = validators_path.with_extension(consts::PENDING_FILE_EXTENSION);
+        if fs::exists(&pending_validators_path)? {
+            fs::rename(&pending_validators_path, &validators_path)?;
+        }
+        Ok(())
+    }
+
+    fn location(&self) -> String {
+        self.bundle_url.to_string()
+    }
+
+    fn repository_type(&self) -> RepositoryType {
+        RepositoryType::Remote
+    }
+}
+
+impl RemoteRepository {
+    /// Creates a repository for the game variant of paths at the base URL url which downloads into the cache
+    /// directories of paths.  Requests are made with http::default_agent.
+    pub fn with_paths(url: Url, paths: Paths) -> Result<Self, FgdbError> {
+        // Url::join replaces the last path segment unless the base ends with a slash.
+        let mut base = url;
+        if !base.path().ends_with('/') {
+            base.set_path(&format!("{}/", base.path()));
+        }
+        let join = |file_name: &str| {
+            let relative = format!("{}/{}/{}", paths.game_variant(), consts::REPOSITORY_ZIP_DIR, file_name);
+            base.join(&relative).map_err(|e| FgdbParseError::InvalidUrl(format!("{}{}: {}", base, relative, e)))
+        };
+        let bundle_url = join(consts::REPOSITORY_ZIP_FILE_NAME)?;
+        let signature_url = join(consts::REPOSITORY_SIGNATURE_FILE_NAME)?;
+        Ok(Self {
+            agent: http::default_agent(),
+            paths,
+            bundle_url,
+            signature_url,
+        })
+    }
+
+    /// Makes requests with agent, e.g., one with the timeouts of a configuration.
+    pub fn agent(mut self, agent: Agent) -> Self {
+        self.agent = agent;
+        self
+    }
+
+    pub fn get_bundle_url(&self) -> &Url {
+        &self.bundle_url
+    }
+
+    // Requests the bundle, returning the response, the number of bytes of the bundle already in the partial download
+    // at temp_path and the validators of the bundle.  If the validators recorded at source_path allow it, only the rest
+    // of the bundle is requested, with If-Range so that the server sends the whole bundle if it has changed.  If the
+    // partial download cannot be resumed, the whole bundle is requested and 0 is returned.
+    fn request_bundle(&self, temp_path: &Path, source_path: &Path) -> Result<(Response<Body>, u64, Validators), FgdbError> {
+        if let Some((bytes, validators, if_range)) = partial_download(temp_path, source_path) {
+            let request = self.agent.get(self.bundle_url.as_str()).header(RANGE, format!("bytes={}-", bytes)).header(IF_RANGE, if_range);
+            match request.call() {
+                Ok(response) if response.status() != StatusCode::PARTIAL_CONTENT => {
+                    let validators = Validators::from_headers(response.headers());
+                    return Ok((response, 0, validators));
+                }
+                Ok(response) if content_range_start(response.headers()) == Some(bytes) => return Ok((response, bytes, validators)),
+                // The partial download is not a prefix of the bundle or the server answered with a different range.
+                Ok(_) | Err(ureq::Error::StatusCode(416)) => {}
+                Err(e) => return Err(self.to_fgdb_error(e)),
+            }
+        }
+        let response = self.agent.get(self.bundle_url.as_str()).call().map_err(|e| self.to_fgdb_error(e))?;
+        let validators = Validators::from_headers(response.headers());
+        Ok((response, 0, validators))
+    }
+
+    // Downloads the signature file of the bundle into the cache zip directory.  If the bundle is unsigned, any cached
+    // signature file is removed so it cannot be mistaken for the signature of the new bundle.
+    fn download_signature(&self) -> Result<(), FgdbError> {
+        let cache_signature_path = self.paths.lookup(Dir::CacheZip).join(consts::REPOSITORY_SIGNATURE_FILE_NAME);
+        match self.agent.get(self.signature_url.as_str()).call() {
+            Ok(mut response) => {
+                let signatures = response.body_mut().read_to_vec().map_err(|e| self.to_fgdb_error(e))?;
+                fs::write(&cache_signature_path, signatures)?;
+            }
+            Err(ureq::Error::StatusCode(404)) => {
+                if fs::exists(&cache_signature_path)? {
+                    fs::remove_file(&cache_signature_path)?;
+                }
+            }
+            Err(e) => return Err(self.to_fgdb_error(e)),
+        }
+        Ok(())
+    }
+
+    fn validators_path(&self) -> PathBuf {
+        self.paths.lookup(Dir::CacheTimestamp).join(consts::VALIDATORS_FILE_NAME)
+    }
+
+    // Client errors, e.g., 404 Not Found, are reported as repository errors since retrying cannot fix them.  I/O errors
+    // raised while talking to the server report the repository as unreachable; other failures are network errors.
+    fn to_fgdb_error(&self, e: ureq::Error) -> FgdbError {
+        match e {
+            ureq::Error::StatusCode(status) if (400..500).contains(&status) => {
+                FgdbError::Repository(format!("'{}' returned HTTP status {}.", self.bundle_url, status), None)
+            }
+            ureq::Error::Io(e) => self.unreachable(e),
+            e => FgdbError::Network(e.to_string(), Some(Box::new(e))),
+        }
+    }
+
+    fn unreachable(&self, e: io::Error) -> FgdbError {
+        FgdbError::Unreachable(format!("'{}' could not be read: {}.", self.bundle_url, e), e)
+    }
+}
+
+// Returns the size of the partial download at temp_path, the validators of the bundle it was taken from, recorded at
+// source_path, and the If-Range value with which to resume it, or None if it cannot be resumed.  If-Range requires a
+// strong ETag or a date.
+fn partial_download(temp_path: &Path, source_path: &Path) -> Option<(u64, Validators, String)> {
+    let bytes = fs::metadata(temp_path).ok()?.len();
+    let validators = Validators::read(source_path).ok()??;
+    let if_range = match (&validators.etag, &validators.last_modified) {
+        (Some(etag), _) if !etag.starts_with("W/") => etag.clone(),
+        (_, Some(last_modified)) => last_modified.clone(),
+        _ => return None,
+    };
+    (bytes > 0).then_some((bytes, validators, if_range))
+}
+
+// Returns the offset of the first byte of a 206 Partial Content response, given by its Content-Range header.
+fn content_range_start(headers: &HeaderMap) -> Option<u64> {
+    let range = headers.get(CONTENT_RANGE)?.to_str().ok()?.strip_prefix("bytes ")?;
+    range.split('-').next()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use crate::config::fgdb_config::get_config;
+    use std::io::{BufRead, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    // Serves body over HTTP on a local port with the ETag in etag, answering requests whose If-None-Match matches it
+    // with 304 Not Modified and range requests whose If-Range matches it with 206 Partial Content.  The status of each
+    // response is recorded in statuses.
+    pub(crate) fn serve(body: Vec<u8>, etag: Arc<Mutex<String>>, statuses: Arc<Mutex<Vec<u16>>>) -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/fgdb", listener.local_addr().unwrap())).unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut lines = Vec::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    lines.push(line.trim().to_string());
+                }
+                let etag = etag.lock().unwrap().clone();
+                let is_head = lines[0].starts_with("HEAD");
+                let header = |name: &str| lines.iter().find_map(|line| line.to_lowercase().strip_prefix(&format!("{}: ", name)).map(str::to_string));
+                let not_modified = header("if-none-match").is_some_and(|value| value == etag.to_lowercase());
+                let range_start = match (header("range"), header("if-range")) {
+                    (Some(range), Some(if_range)) if if_range == etag.to_lowercase() => {
+                        range.strip_prefix("bytes=").and_then(|range| range.trim_end_matches('-').parse::<usize>().ok())
+                    }
+                    _ => None,
+                };
+                let (status, reason, content) = match range_start {
+                    _ if not_modified => (304, "Not Modified", &body[..0]),
+                    Some(start) => (206, "Partial Content", &body[start..]),
+                    None => (200, "OK", &body[..]),
+                };
+                statuses.lock().unwrap().push(status);
+                let mut response = format!("HTTP/1.1 {} {}\r\nETag: {}\r\nConnection: close\r\n", status, reason, etag).into_bytes();
+                if let Some(start) = range_start {
+                    response.extend(format!("Content-Range: bytes {}-{}/{}\r\n", start, body.len() - 1, body.len()).bytes());
+                }
+                if status != 304 {
+                    response.extend(format!("Content-Length: {}\r\n", content.len()).bytes());
+                }
+                response.extend(b"\r\n");
+                if !is_head {
+                    response.extend(content);
+                }
+                stream.write_all(&response).unwrap();
+            }
+        });
+        url
+    }
+
+    // Returns paths whose cache is under a directory of its own, so that tests running in parallel do not share a cache.
+    fn isolated_paths(env_guard: &EnvPoolGuard, test_name: &str) -> Paths {
+        Paths::with_root(&env_guard.out_dir(test_name), get_config().game_variant, env_guard.env)
+    }
+
+    #[test]
+    fn test_with_paths_builds_bundle_url() {
+        let paths = Paths::with_root(Path::new("data"), crate::GameVariant::Poe2, crate::util::env::Env::Prod);
+        let repo = RemoteRepository::with_paths(Url::parse("https://example.com/fgdb").unwrap(), paths).unwrap();
+        assert_eq!("https://example.com/fgdb/poe2/zip/poedb.zip", repo.get_bundle_url().as_str());
+        assert_eq!("https://example.com/fgdb/poe2/zip/poedb.zip.sig", repo.signature_url.as_str());
+    }
+
+    #[test]
+    fn test_freshness_uses_conditional_requests() {
+        let env_guard = EnvPoolGuard::new();
+        let paths = isolated_paths(&env_guard, "test_freshness_uses_conditional_requests");
+        paths.create_dirs([Dir::CacheZip, Dir::CacheTimestamp].iter()).unwrap();
+        let _ = fs::remove_file(paths.lookup(Dir::CacheTimestamp).join(consts::VALIDATORS_FILE_NAME));
+        let etag = Arc::new(Mutex::new("\"v1\"".to_string()));
+        let statuses = Arc::new(Mutex::new(Vec::new()));
+        let body = vec![7u8; 100_000];
+        let repo = RemoteRepository::with_paths(serve(body.clone(), Arc::clone(&etag), Arc::clone(&statuses)), paths.clone()).unwrap();
+
+        // Without validators no request is made.
+        assert!(!repo.is_cache_current().unwrap());
+        assert!(statuses.lock().unwrap().is_empty());
+
+        let reports = Mutex::new(Vec::new());
+        let observer = |progress: &Progress| reports.lock().unwrap().push(progress.clone());
+        repo.download_with_progress(&observer, &CancellationToken::new()).unwrap();
+        assert_eq!(body, fs::read(paths.lookup(Dir::CacheZip).join(consts::REPOSITORY_ZIP_FILE_NAME)).unwrap());
+        assert_eq!(Some(&Progress::Downloading { bytes: 100_000, total: 100_000 }), reports.lock().unwrap().last());
+
+        // The download does not make the cache current until it has been imported.
+        assert!(!repo.is_cache_current().unwrap());
+        assert_eq!(None, repo.cached_version());
+        repo.mark_cache_current().unwrap();
+        assert_eq!(Some("\"v1\"".to_string()), repo.cached_version());
+
+        // An unchanged bundle costs a single 304 response.
+        statuses.lock().unwrap().clear();
+        assert!(repo.is_cache_current().unwrap());
+        assert_eq!(vec![304], *statuses.lock().unwrap());
+
+        *etag.lock().unwrap() = "\"v2\"".to_string();
+        assert!(!repo.is_cache_current().unwrap());
+    }
+
+    #[test]
+    fn test_download_resumes_partial_download() {
+        let env_guard = EnvPoolGuard::new();
+        let paths = isolated_paths(&env_guard, "test_download_resumes_partial_download");
+        paths.create_dirs([Dir::CacheZip, Dir::CacheTimestamp].iter()).unwrap();
+        let cache_path = paths.lookup(Dir::CacheZip).join(consts::REPOSITORY_ZIP_FILE_NAME);
+        let temp_path = cache_path.with_extension(consts::TEMP_FILE_EXTENSION);
+        let source_path = cache_path.with_extension(consts::PARTIAL_SOURCE_FILE_EXTENSION);
+        let statuses = Arc::new(Mutex::new(Vec::new()));
+        let body = (0..100_000).map(|i| i as u8).collect::<Vec<_>>();
+        let url = serve(body.clone(), Arc::new(Mutex::new("\"v1\"".to_string())), Arc::clone(&statuses));
+        let repo = RemoteRepository::with_paths(url, paths.clone()).unwrap();
+        let write_partial_download = |contents: &[u8], etag: &str| {
+            fs::write(&temp_path, contents).unwrap();
+            Validators { etag: Some(etag.to_string()), last_modified: None }.write(&source_path).unwrap();
+        };
+
+        // Only the rest of an unchanged bundle is downloaded.
+        write_partial_download(&body[..40_000], "\"v1\"");
+        let reports = Mutex::new(Vec::new());
+        let observer = |progress: &Progress| reports.lock().unwrap().push(progress.clone());
+        repo.download_with_progress(&observer, &CancellationToken::new()).unwrap();
+        assert_eq!(Some(206), statuses.lock().unwrap().first().copied());
+        assert_eq!(Some(&Progress::Downloading { bytes: 40_000, total: 100_000 }), reports.lock().unwrap().first());
+        assert_eq!(body, fs::read(&cache_path).unwrap());
+        assert!(!temp_path.exists() && !source_path.exists());
+
+        // A partial download of a bundle which has since changed is replaced by the whole bundle.
+        statuses.lock().unwrap().clear();
+        write_partial_download(&[0u8; 40_000], "\"v0\"");
+        repo.download().unwrap();
+        assert_eq!(Some(200), statuses.lock().unwrap().first().copied());
+        assert_eq!(body, fs::read(&cache_path).unwrap());
+    }
+
+    #[test]
+    fn test_requests_time_out() {
+        let env_guard = EnvPoolGuard::new();
+        let paths = isolated_paths(&env_guard, "test_requests_time_out");
+        paths.create_dirs([Dir::CacheZip, Dir::CacheTimestamp].iter()).unwrap();
+        // The server accepts connections but never responds.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/fgdb", listener.local_addr().unwrap())).unwrap();
+        thread::spawn(move || listener.incoming().collect::<Vec<_>>());
+        let agent = http::create_agent(Duration::from_secs(1), Duration::from_millis(200));
+        let repo = RemoteRepository::with_paths(url, paths).unwrap().agent(agent);
+
+        let start = Instant::now();
+        let result = repo.download();
+        assert!(matches!(&result, Err(e) if e.is_transient()), "{:?}", result);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_download_async_works() {
+        use crate::concurrency::blocking_task::tests::block_on;
+
+        let env_guard = EnvPoolGuard::new();
+        let paths = isolated_paths(&env_guard, "test_download_async_works");
+        paths.create_dirs([Dir::CacheZip, Dir::CacheTimestamp].iter()).unwrap();
+        let body = vec![3u8; 1000];
+        let url = serve(body.clone(), Arc::new(Mutex::new("\"v1\"".to_string())), Arc::new(Mutex::new(Vec::new())));
+        let repo: Box<dyn Repository> = Box::new(RemoteRepository::with_paths(url, paths.clone()).unwrap());
+        block_on(repo.download_async(CancellationToken::new())).unwrap();
+        assert_eq!(body, fs::read(paths.lookup(Dir::CacheZip).join(consts::REPOSITORY_ZIP_FILE_NAME)).unwrap());
+    }
+}
@@ -2,20 +2,62 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 2/25/2026.
 
-use crate::GameVariant;
-use anyhow::{Error, Result};
+#[cfg(feature = "async")]
+use crate::concurrency::blocking_task::BlockingTask;
+use crate::concurrency::cancellation_token::CancellationToken;
+use crate::config::fgdb_config::FgdbConfig;
+use crate::fs::paths::Paths;
+use crate::repo::local_repository::LocalRepository;
+use crate::repo::remote_repository::RemoteRepository;
+use crate::types::repository::Repository as RepositoryType;
+use crate::update::progress::ProgressObserver;
+use crate::util::errors::FgdbError;
+use crate::util::http;
 
 pub trait Repository {
-    /// Downloads a zip file containing all the JSON files in the repository to the cache/zip directory.
-    fn download(&self) -> Result<(), Error>;
+    /// Downloads a zip file containing all the JSON files in the repository to the cache/zip directory.  The freshness
+    /// marker of the download, e.g., the repository timestamp, is kept pending until mark_cache_current is called, so
+    /// a bundle which is never imported does not make the cache current.
+    fn download(&self) -> Result<(), FgdbError>;
 
-    /// Compares the timestamp in the cache/timestamp directory against the timestamp in the repository.  If
-    /// the cache is at least as new as the repository, true is returned, otherwise false is returned.  If the
-    /// timestamp file is missing from the cache/timestamp directory, false is returned.
-    fn is_cache_current(&self) -> Result<bool, Error>;
+    /// Downloads as download does, reporting Progress::Downloading to observer and stopping with
+    /// FgdbError::Cancelled if token is cancelled.  The default implementation checks token only before downloading
+    /// and makes no reports.
+    fn download_with_progress(&self, _observer: &dyn ProgressObserver, token: &CancellationToken) -> Result<(), FgdbError> {
+        token.check()?;
+        self.download()
+    }
+
+    /// Downloads as download does on the blocking worker pool.  The download stops with FgdbError::Cancelled if token is
+    /// cancelled.
+    #[cfg(feature = "async")]
+    fn download_async(&self, token: CancellationToken) -> BlockingTask<Result<(), FgdbError>>;
+
+    /// Returns true if the cache is at least as new as the repository, otherwise false.  If the cache has not been
+    /// downloaded, false is returned.
+    fn is_cache_current(&self) -> Result<bool, FgdbError>;
+
+    /// Returns the version of the imported bundle as recorded in the cache, e.g., the repository timestamp or the ETag
+    /// of the bundle, or None if no bundle has been imported.
+    fn cached_version(&self) -> Option<String>;
+
+    /// Records the last download as the contents of the cache once it has been extracted and imported.  Does nothing
+    /// if nothing has been downloaded since the last call.
+    fn mark_cache_current(&self) -> Result<(), FgdbError>;
+
+    /// Returns the path or URL of the repository.
+    fn location(&self) -> String;
+
+    fn repository_type(&self) -> RepositoryType;
 }
 
-/// Obtains settings from fgdb.toml and then creates a compatible repository.
-pub fn create_repository(_game_variant: GameVariant) -> Result<Box<dyn Repository>, Error> {
-    todo!("create_repository not yet implemented");
+/// Creates the repository described by config which downloads into the cache directories of paths.
+pub fn create_repository(config: &FgdbConfig, paths: Paths) -> Result<Box<dyn Repository>, FgdbError> {
+    match config.repository {
+        RepositoryType::Local => Ok(Box::new(LocalRepository::with_paths(config.root_path.clone(), paths))),
+        RepositoryType::Remote => match &config.repository_url {
+            Some(url) => Ok(Box::new(RemoteRepository::with_paths(url.clone(), paths)?.agent(http::agent(config)))),
+            None => Err(FgdbError::Config("repository_url must be set when repository is \"Remote\".".to_string(), None)),
+        },
+    }
 }
@@ -0,0 +1,117 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::concurrency::cancellation_token::CancellationToken;
+use crate::util::consts;
+use crate::util::errors::FgdbError;
+use std::time::Duration;
+
+/// How a failed repository operation is retried.  Only transient errors, as given by FgdbError::is_transient, are
+/// retried.  The delay before the nth retry is initial_backoff * multiplier^(n - 1), capped at max_backoff.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryPolicy {
+    // The number of attempts made, including the first.  A value of 1 disables retries.
+    pub max_attempts: u32,
+
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: consts::REPO_RETRY_MAX_ATTEMPTS,
+            initial_backoff: Duration::from_millis(consts::REPO_RETRY_INITIAL_BACKOFF_MS),
+            max_backoff: Duration::from_millis(consts::REPO_RETRY_MAX_BACKOFF_MS),
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns a policy which makes a single attempt.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// Returns the delay before the given retry, counting from 1.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.max(1.0).powi(retry.saturating_sub(1).min(i32::MAX as u32) as i32);
+        self.initial_backoff.mul_f64(factor.min(u32::MAX as f64)).min(self.max_backoff)
+    }
+
+    /// Runs operation until it succeeds, fails with an error which is not transient, or has been attempted
+    /// max_attempts times.  token is checked before each attempt and while waiting between attempts.
+    pub fn run<T>(&self, token: &CancellationToken, mut operation: impl FnMut() -> Result<T, FgdbError>) -> Result<T, FgdbError> {
+        let mut attempt = 1;
+        loop {
+            token.check()?;
+            match operation() {
+                Err(e) if e.is_transient() && attempt < self.max_attempts => {
+                    token.sleep(self.backoff(attempt))?;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(3),
+            multiplier: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_exponentially_up_to_max() {
+        let policy = policy();
+        assert_eq!(Duration::from_millis(1), policy.backoff(1));
+        assert_eq!(Duration::from_millis(2), policy.backoff(2));
+        assert_eq!(Duration::from_millis(3), policy.backoff(3));
+        assert_eq!(Duration::from_millis(3), policy.backoff(100));
+    }
+
+    #[test]
+    fn test_run_retries_only_transient_errors() {
+        let token = CancellationToken::new();
+        let mut attempts = 0;
+        let result: Result<(), FgdbError> = policy().run(&token, || {
+            attempts += 1;
+            Err(FgdbError::Network("timed out".to_string(), None))
+        });
+        assert!(matches!(result, Err(FgdbError::Network(..))));
+        assert_eq!(3, attempts);
+
+        let mut attempts = 0;
+        let result = policy().run(&token, || {
+            attempts += 1;
+            match attempts {
+                1 => Err(FgdbError::Io(io::Error::from(io::ErrorKind::TimedOut))),
+                _ => Ok(attempts),
+            }
+        });
+        assert_eq!(2, result.unwrap());
+
+        let mut attempts = 0;
+        let result: Result<(), FgdbError> = policy().run(&token, || {
+            attempts += 1;
+            Err(FgdbError::Io(io::Error::from(io::ErrorKind::NotFound)))
+        });
+        assert!(result.is_err());
+        assert_eq!(1, attempts);
+    }
+}
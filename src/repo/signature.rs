@@ -0,0 +1,96 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::types::public_key::PublicKey;
+use crate::util::errors::FgdbError;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use std::fs;
+use std::path::Path;
+
+// Bundles are signed by fgsvr with a detached signature file stored next to the bundle as poedb.zip.sig.  The file holds
+// one standard base64 Ed25519 signature of the bundle per line; blank lines and lines starting with '#' are ignored.  A
+// bundle is accepted if any of its signatures verifies under any configured key, so keys are rotated by signing with
+// both the old and the new key until every client trusts the new key, then retiring the old key.
+
+/// Verifies the bundle at zip_path against the signature file at signature_path, returning FgdbError::Signature if
+/// the bundle is unsigned or if none of its signatures was made by one of keys.
+pub fn verify_bundle(zip_path: &Path, signature_path: &Path, keys: &[PublicKey]) -> Result<(), FgdbError> {
+    if !fs::exists(signature_path)? {
+        return Err(FgdbError::Signature(format!("'{}' is not signed.", zip_path.display())));
+    }
+    let bundle = fs::read(zip_path)?;
+    let signatures = fs::read_to_string(signature_path)?;
+    let is_verified = signatures
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| STANDARD.decode(line).ok())
+        .any(|signature| keys.iter().any(|key| key.verify(&bundle, &signature)));
+    if !is_verified {
+        return Err(FgdbError::Signature(format!("'{}' is not signed by a trusted key.", zip_path.display())));
+    }
+    Ok(())
+}
+
+/// Signs the bundle at zip_path with each of the PKCS#8 encoded Ed25519 key pairs in key_pairs, writing the signatures
+/// to signature_path.  Signing with more than one key pair is used while keys are rotated.
+pub fn sign_bundle(zip_path: &Path, signature_path: &Path, key_pairs: &[&[u8]]) -> Result<(), FgdbError> {
+    let bundle = fs::read(zip_path)?;
+    let mut signatures = String::new();
+    for pkcs8 in key_pairs {
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8).map_err(|e| FgdbError::Signature(format!("Invalid key pair: {}.", e)))?;
+        signatures.push_str(&STANDARD.encode(key_pair.sign(&bundle)));
+        signatures.push('\n');
+    }
+    fs::write(signature_path, signatures)?;
+    Ok(())
+}
+
+/// Returns the public key of the PKCS#8 encoded Ed25519 key pair pkcs8.
+pub fn public_key(pkcs8: &[u8]) -> Result<PublicKey, FgdbError> {
+    let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8).map_err(|e| FgdbError::Signature(format!("Invalid key pair: {}.", e)))?;
+    let bytes = key_pair.public_key().as_ref().try_into().map_err(|_| FgdbError::Signature("Invalid public key.".to_string()))?;
+    Ok(PublicKey::from_bytes(bytes))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use ring::rand::SystemRandom;
+
+    // Generates a PKCS#8 encoded Ed25519 key pair.
+    pub(crate) fn generate_key_pair() -> Vec<u8> {
+        Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap().as_ref().to_vec()
+    }
+
+    #[test]
+    fn test_verify_bundle_works() {
+        let env_guard = EnvPoolGuard::new();
+        let dir = env_guard.out_dir("test_verify_bundle_works");
+        let zip_path = dir.join("poedb.zip");
+        let signature_path = dir.join("poedb.zip.sig");
+        fs::write(&zip_path, b"bundle").unwrap();
+        let (old, new) = (generate_key_pair(), generate_key_pair());
+        let (old_key, new_key) = (public_key(&old).unwrap(), public_key(&new).unwrap());
+
+        let result = verify_bundle(&zip_path, &signature_path, &[old_key]);
+        assert!(matches!(result, Err(FgdbError::Signature(message)) if message.contains("is not signed.")));
+
+        // During rotation the bundle is signed with both keys and verifies under either.
+        sign_bundle(&zip_path, &signature_path, &[&old, &new]).unwrap();
+        verify_bundle(&zip_path, &signature_path, &[old_key]).unwrap();
+        verify_bundle(&zip_path, &signature_path, &[new_key]).unwrap();
+
+        sign_bundle(&zip_path, &signature_path, &[&new]).unwrap();
+        assert!(matches!(verify_bundle(&zip_path, &signature_path, &[old_key]), Err(FgdbError::Signature(_))));
+        verify_bundle(&zip_path, &signature_path, &[old_key, new_key]).unwrap();
+
+        // A tampered bundle fails verification.
+        fs::write(&zip_path, b"bundle!").unwrap();
+        assert!(matches!(verify_bundle(&zip_path, &signature_path, &[new_key]), Err(FgdbError::Signature(_))));
+    }
+}
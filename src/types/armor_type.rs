@@ -3,7 +3,6 @@
 // Created by Hankinsohl on 2/2/2026.
 
 use crate::util::errors::FgdbParseError;
-use anyhow::Result;
 use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
 use rusqlite::{Result as RusqliteResult, ToSql};
 use serde::{Deserialize, Serialize};
@@ -83,21 +82,19 @@ impl ToSql for ArmorType {
 
 impl ArmorType {
     pub fn from_stats(armour: u32, evasion: u32, energy_shield: u32) -> ArmorType {
-        match (armour, evasion, energy_shield) {
-            (a, e, es) if a == 0 && e == 0 && es == 0 => ArmorType::Cloth,
+        match (armour > 0, evasion > 0, energy_shield > 0) {
+            (false, false, false) => ArmorType::Cloth,
 
-            (a, e, es) if a > 0 && e == 0 && es == 0 => ArmorType::Plate,
-            (a, e, es) if a == 0 && e > 0 && es == 0 => ArmorType::Leather,
-            (a, e, es) if a == 0 && e == 0 && es > 0 => ArmorType::Cloth,
+            (true, false, false) => ArmorType::Plate,
+            (false, true, false) => ArmorType::Leather,
+            (false, false, true) => ArmorType::Cloth,
 
-            (a, e, es) if a > 0 && e > 0 && es == 0 => ArmorType::Scale,
-            (a, e, es) if a > 0 && e == 0 && es > 0 => ArmorType::Chain,
+            (true, true, false) => ArmorType::Scale,
+            (true, false, true) => ArmorType::Chain,
 
-            (a, e, es) if a == 0 && e > 0 && es > 0 => ArmorType::Padded,
+            (false, true, true) => ArmorType::Padded,
 
-            (a, e, es) if a > 0 && e > 0 && es > 0 => ArmorType::Mail,
-
-            _ => panic!("All 8 cases should have been covered above."),
+            (true, true, true) => ArmorType::Mail,
         }
     }
 }
@@ -117,6 +114,18 @@ mod tests {
         assert_eq!(ArmorType::from_str("Scale").unwrap(), ArmorType::Scale);
     }
 
+    #[test]
+    fn test_from_stats_works() {
+        assert_eq!(ArmorType::Cloth, ArmorType::from_stats(0, 0, 0));
+        assert_eq!(ArmorType::Plate, ArmorType::from_stats(10, 0, 0));
+        assert_eq!(ArmorType::Leather, ArmorType::from_stats(0, 10, 0));
+        assert_eq!(ArmorType::Cloth, ArmorType::from_stats(0, 0, 10));
+        assert_eq!(ArmorType::Scale, ArmorType::from_stats(10, 10, 0));
+        assert_eq!(ArmorType::Chain, ArmorType::from_stats(10, 0, 10));
+        assert_eq!(ArmorType::Padded, ArmorType::from_stats(0, 10, 10));
+        assert_eq!(ArmorType::Mail, ArmorType::from_stats(10, 10, 10));
+    }
+
     #[test]
     fn test_from_str_with_invalid_input_yields_parse_error_invalid_liquidity() {
         assert!(matches!(ArmorType::from_str("mail"), Err(FgdbParseError::InvalidArmorType(_))));
@@ -3,10 +3,10 @@
 // Created by Hankinsohl on 2/6/2026.
 
 use crate::util::errors::FgdbRangeError;
-use anyhow::Result;
+use crate::util::json;
 use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
 use rusqlite::{Result as RusqliteResult, ToSql};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::fmt::Display;
 use std::ops::RangeInclusive;
@@ -15,11 +15,17 @@ pub const MIN_FONT_SIZE: u8 = 18;
 pub const MAX_FONT_SIZE: u8 = 45;
 pub const FONT_SIZE_RANGE: RangeInclusive<u8> = MIN_FONT_SIZE..=MAX_FONT_SIZE;
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct FontSize {
     size: u8,
 }
 
+impl<'de> Deserialize<'de> for FontSize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        FontSize::new(json::deserialize_newtype(deserializer, "size")?).map_err(json::range_error)
+    }
+}
+
 impl Display for FontSize {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.size)
@@ -38,6 +44,12 @@ impl ToSql for FontSize {
     }
 }
 
+impl Serialize for FontSize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        json::serialize_newtype(serializer, "FontSize", "size", &self.size)
+    }
+}
+
 impl FontSize {
     pub fn new(size: u8) -> Result<Self, FgdbRangeError> {
         if !FONT_SIZE_RANGE.contains(&size) {
@@ -0,0 +1,70 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::util::errors::FgdbParseError;
+use serde::de::Error as DeError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::fmt::Display;
+
+/// The JSON format used by table import and export.  Nested, the original format, represents scalar newtypes
+/// such as Price and StackSize as objects, e.g., "price": {"price": 3.1}.  Flat represents them as bare values,
+/// e.g., "price": 3.1.  Import accepts either format regardless of version.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum FormatVersion {
+    #[default]
+    Nested,
+    Flat,
+}
+
+impl<'de> Deserialize<'de> for FormatVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        FormatVersion::try_from(u32::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl Display for FormatVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.version())
+    }
+}
+
+impl Serialize for FormatVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.version())
+    }
+}
+
+impl TryFrom<u32> for FormatVersion {
+    type Error = FgdbParseError;
+    fn try_from(version: u32) -> Result<Self, FgdbParseError> {
+        match version {
+            1 => Ok(FormatVersion::Nested),
+            2 => Ok(FormatVersion::Flat),
+            _ => Err(FgdbParseError::InvalidFormatVersion(version)),
+        }
+    }
+}
+
+impl FormatVersion {
+    /// Returns the version number recorded in the repository manifest.
+    pub fn version(&self) -> u32 {
+        match self {
+            FormatVersion::Nested => 1,
+            FormatVersion::Flat => 2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_works() {
+        assert_eq!(FormatVersion::Nested, FormatVersion::try_from(1).unwrap());
+        assert_eq!(FormatVersion::Flat, FormatVersion::try_from(2).unwrap());
+        assert!(matches!(FormatVersion::try_from(3), Err(FgdbParseError::InvalidFormatVersion(3))));
+    }
+}
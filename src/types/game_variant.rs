@@ -2,9 +2,11 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 1/16/2026.
 
+use crate::util::errors::FgdbParseError;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Display;
+use std::str::FromStr;
 use strum_macros::EnumIter;
 
 #[derive(Clone, Copy, Debug, Default, Deserialize, EnumIter, Eq, Ord, PartialEq, PartialOrd, Serialize)]
@@ -26,3 +28,15 @@ impl Display for GameVariant {
         )
     }
 }
+
+// Accepts both the serialized form, e.g., "Poe1", and the displayed form, e.g., "poe1".
+impl FromStr for GameVariant {
+    type Err = FgdbParseError;
+    fn from_str(s: &str) -> Result<GameVariant, FgdbParseError> {
+        match s {
+            "Poe1" | "poe1" => Ok(GameVariant::Poe1),
+            "Poe2" | "poe2" => Ok(GameVariant::Poe2),
+            _ => Err(FgdbParseError::InvalidGameVariant(s.to_string())),
+        }
+    }
+}
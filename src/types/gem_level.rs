@@ -3,10 +3,10 @@
 // Created by Hankinsohl on 2/7/2026.
 
 use crate::util::errors::FgdbRangeError;
-use anyhow::Result;
+use crate::util::json;
 use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
 use rusqlite::{Result as RusqliteResult, ToSql};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::fmt::Display;
 use std::ops::RangeInclusive;
@@ -15,11 +15,17 @@ pub const MIN_GEM_LEVEL: u8 = 1;
 pub const MAX_GEM_LEVEL: u8 = 21;
 pub const GEM_LEVEL_RANGE: RangeInclusive<u8> = MIN_GEM_LEVEL..=MAX_GEM_LEVEL;
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct GemLevel {
     level: u8,
 }
 
+impl<'de> Deserialize<'de> for GemLevel {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        GemLevel::new(json::deserialize_newtype(deserializer, "level")?).map_err(json::range_error)
+    }
+}
+
 impl Display for GemLevel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.level)
@@ -38,6 +44,12 @@ impl ToSql for GemLevel {
     }
 }
 
+impl Serialize for GemLevel {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        json::serialize_newtype(serializer, "GemLevel", "level", &self.level)
+    }
+}
+
 impl GemLevel {
     pub fn new(level: u8) -> Result<Self, FgdbRangeError> {
         if !GEM_LEVEL_RANGE.contains(&level) {
@@ -6,7 +6,6 @@ use crate::types::icon_shape::IconShape;
 use crate::types::icon_size::IconSize;
 use crate::types::stock_color::StockColor;
 use crate::util::errors::FgdbFromSqlError;
-use anyhow::Result;
 use rusqlite::Error as RusqliteError;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -3,10 +3,10 @@
 // Created by Hankinsohl on 2/6/2026.
 
 use crate::util::errors::FgdbRangeError;
-use anyhow::Result;
+use crate::util::json;
 use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
 use rusqlite::{Result as RusqliteResult, ToSql};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::fmt::Display;
 use std::ops::RangeInclusive;
@@ -15,11 +15,17 @@ pub const MIN_ICON_SIZE: u8 = 0;
 pub const MAX_ICON_SIZE: u8 = 2;
 pub const ICON_SIZE_RANGE: RangeInclusive<u8> = MIN_ICON_SIZE..=MAX_ICON_SIZE;
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct IconSize {
     size: u8,
 }
 
+impl<'de> Deserialize<'de> for IconSize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        IconSize::new(json::deserialize_newtype(deserializer, "size")?).map_err(json::range_error)
+    }
+}
+
 impl Display for IconSize {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.size)
@@ -38,6 +44,12 @@ impl ToSql for IconSize {
     }
 }
 
+impl Serialize for IconSize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        json::serialize_newtype(serializer, "IconSize", "size", &self.size)
+    }
+}
+
 impl IconSize {
     pub fn new(size: u8) -> Result<Self, FgdbRangeError> {
         if !ICON_SIZE_RANGE.contains(&size) {
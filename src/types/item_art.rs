@@ -0,0 +1,165 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::util::errors::FgdbFromSqlError;
+use base64::Engine;
+use base64::alphabet;
+use base64::engine::DecodePaddingMode;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use rusqlite::Error as RusqliteError;
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+// Path segments preceding the encoded art specification in generated image URLs, e.g.,
+// https://web.poecdn.com/gen/image/<specification>/<hash>/<file>.png
+const GENERATED_IMAGE_PREFIX: [&str; 2] = ["gen", "image"];
+
+// The specification is base64 encoded using the URL-safe alphabet; padding is normally omitted.
+const SPECIFICATION_ENGINE: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+// The art specification as encoded in the URL: [<version>, <version>, {"f": ..., "w": ..., "h": ..., ...}].  Items
+// occupying a single inventory cell omit "w" and "h".
+#[derive(Deserialize)]
+struct Specification(IgnoredAny, IgnoredAny, SpecificationFields);
+
+#[derive(Deserialize)]
+struct SpecificationFields {
+    f: String,
+    w: Option<u8>,
+    h: Option<u8>,
+    scale: Option<u8>,
+    fractured: Option<bool>,
+}
+
+/// Item art decoded from a trade API image URL.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct ItemArt {
+    // The path to the art within the game's content, e.g., 2DItems/Armours/BodyArmours/BodyStr1A.
+    pub art: String,
+
+    // The width of the item in inventory cells.
+    pub width: u8,
+
+    // The height of the item in inventory cells.
+    pub height: u8,
+
+    // The scale at which the image is rendered.
+    pub scale: u8,
+
+    // True if the image depicts the fractured variant of the item.
+    pub is_fractured: bool,
+}
+
+impl ItemArt {
+    /// Decodes the item art embedded in a generated image URL.  Returns None if url is not a generated image URL, as
+    /// is the case for images hosted on the wiki, or if its specification cannot be decoded.  A width, height or scale
+    /// of zero is treated as one.
+    pub fn from_url(url: &Url) -> Option<Self> {
+        let mut segments = url.path_segments()?;
+        if !GENERATED_IMAGE_PREFIX.iter().all(|prefix| segments.next() == Some(*prefix)) {
+            return None;
+        }
+        let decoded = SPECIFICATION_ENGINE.decode(segments.next()?).ok()?;
+        let Specification(_, _, fields) = serde_json::from_slice(&decoded).ok()?;
+        Some(ItemArt {
+            art: fields.f,
+            width: fields.w.unwrap_or(1).max(1),
+            height: fields.h.unwrap_or(1).max(1),
+            scale: fields.scale.unwrap_or(1).max(1),
+            is_fractured: fields.fractured.unwrap_or(false),
+        })
+    }
+
+    // Constructs an ItemArt using arguments as stored in the database.
+    pub fn from_sql(
+        art: Option<String>,
+        width: Option<u8>,
+        height: Option<u8>,
+        scale: Option<u8>,
+        is_fractured: Option<bool>,
+    ) -> Result<Option<Self>, RusqliteError> {
+        match (art, width, height, scale, is_fractured) {
+            (None, None, None, None, None) => Ok(None),
+            (Some(art), Some(width), Some(height), Some(scale), Some(is_fractured)) => Ok(Some(ItemArt {
+                art,
+                width,
+                height,
+                scale,
+                is_fractured,
+            })),
+            _ => Err(FgdbFromSqlError::ItemArt("art, width, height, scale and is_fractured must be all Some or all None".to_string()).into()),
+        }
+    }
+
+    /// Returns the number of inventory cells occupied by the item.
+    pub fn area(&self) -> u16 {
+        self.width as u16 * self.height as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(url: &str) -> Option<ItemArt> {
+        ItemArt::from_url(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn test_from_url_works() {
+        let art = parse("https://web.poecdn.com/gen/image/WzI1LDE0LHsiZiI6IjJESXRlbXMvQXJtb3Vycy9Cb2R5QXJtb3Vycy9Cb2R5RGV4M0MiLCJ3IjoyLCJoIjozLCJzY2FsZSI6MSwiZnJhY3R1cmVkIjp0cnVlfV0/ea8240a665/BodyDex3C.png")
+            .unwrap();
+        assert_eq!("2DItems/Armours/BodyArmours/BodyDex3C", art.art);
+        assert_eq!(2, art.width);
+        assert_eq!(3, art.height);
+        assert_eq!(1, art.scale);
+        assert!(art.is_fractured);
+        assert_eq!(6, art.area());
+    }
+
+    #[test]
+    fn test_from_url_defaults_to_single_cell() {
+        let art = parse("https://web.poecdn.com/gen/image/WzI1LDE0LHsiZiI6IjJESXRlbXMvQ3VycmVuY3kvQ3VycmVuY3lSZXJvbGxSYXJlIiwic2NhbGUiOjF9XQ/d119a0d734/CurrencyRerollRare.png")
+            .unwrap();
+        assert_eq!("2DItems/Currency/CurrencyRerollRare", art.art);
+        assert_eq!(1, art.width);
+        assert_eq!(1, art.height);
+        assert!(!art.is_fractured);
+    }
+
+    #[test]
+    fn test_from_url_returns_none_for_other_urls() {
+        assert_eq!(None, parse("https://www.poewiki.net/images/6/60/Stacked_Deck_inventory_icon.png"));
+
+        // The generated image prefix must begin the path.
+        assert_eq!(None, parse("https://www.poewiki.net/images/image/6/60/Stacked_Deck_inventory_icon.png"));
+    }
+
+    #[test]
+    fn test_from_url_with_invalid_specification_returns_none() {
+        assert_eq!(None, parse("https://web.poecdn.com/gen/image/!!!/x/y.png"));
+        assert_eq!(None, parse("https://web.poecdn.com/gen/image/e30/x/y.png"));
+        assert_eq!(None, parse("https://web.poecdn.com/gen/image"));
+    }
+
+    #[test]
+    fn test_from_url_treats_zero_dimensions_as_one() {
+        let spec = SPECIFICATION_ENGINE.encode(r#"[25,14,{"f":"2DItems/Rings/Ring7","w":0,"h":0,"scale":0}]"#);
+        let art = parse(&format!("https://web.poecdn.com/gen/image/{}/x/Ring7.png", spec)).unwrap();
+        assert_eq!((1, 1, 1), (art.width, art.height, art.scale));
+    }
+
+    #[test]
+    fn test_from_sql_works() {
+        assert_eq!(None, ItemArt::from_sql(None, None, None, None, None).unwrap());
+        let art = ItemArt::from_sql(Some("2DItems/Rings/Ring7".to_string()), Some(1), Some(1), Some(1), Some(false)).unwrap();
+        assert_eq!(1, art.unwrap().area());
+        let result = ItemArt::from_sql(Some("2DItems/Rings/Ring7".to_string()), None, Some(1), Some(1), Some(false));
+        assert!(matches!(result, Err(RusqliteError::FromSqlConversionFailure(..))));
+    }
+}
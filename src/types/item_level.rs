@@ -3,10 +3,10 @@
 // Created by Hankinsohl on 2/7/2026.
 
 use crate::util::errors::FgdbRangeError;
-use anyhow::Result;
+use crate::util::json;
 use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
 use rusqlite::{Result as RusqliteResult, ToSql};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::fmt::Display;
 use std::ops::RangeInclusive;
@@ -15,11 +15,17 @@ pub const MIN_ITEM_LEVEL: u8 = 0;
 pub const MAX_ITEM_LEVEL: u8 = 100;
 pub const ITEM_LEVEL_RANGE: RangeInclusive<u8> = MIN_ITEM_LEVEL..=MAX_ITEM_LEVEL;
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct ItemLevel {
     level: u8,
 }
 
+impl<'de> Deserialize<'de> for ItemLevel {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ItemLevel::new(json::deserialize_newtype(deserializer, "level")?).map_err(json::range_error)
+    }
+}
+
 impl Display for ItemLevel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.level)
@@ -38,6 +44,12 @@ impl ToSql for ItemLevel {
     }
 }
 
+impl Serialize for ItemLevel {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        json::serialize_newtype(serializer, "ItemLevel", "level", &self.level)
+    }
+}
+
 impl ItemLevel {
     pub fn new(level: u8) -> Result<Self, FgdbRangeError> {
         if !ITEM_LEVEL_RANGE.contains(&level) {
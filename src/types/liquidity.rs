@@ -3,7 +3,6 @@
 // Created by Hankinsohl on 1/30/2026.
 
 use crate::util::errors::FgdbParseError;
-use anyhow::Result;
 use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
 use rusqlite::{Result as RusqliteResult, ToSql};
 use serde::{Deserialize, Serialize};
@@ -5,15 +5,18 @@
 pub mod armor_type;
 pub mod color_basis;
 pub mod font_size;
+pub mod format_version;
 pub mod game_variant;
 pub mod gem_level;
 pub mod icon;
 pub mod icon_shape;
 pub mod icon_size;
+pub mod item_art;
 pub mod item_level;
 pub mod liquidity;
 pub mod non_unique_rarity;
 pub mod price;
+pub mod public_key;
 pub mod rarity;
 pub mod repository;
 pub mod sound;
@@ -3,20 +3,26 @@
 // Created by Hankinsohl on 2/7/2026.
 
 use crate::util::errors::FgdbRangeError;
-use anyhow::Result;
+use crate::util::json;
 use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
 use rusqlite::{Result as RusqliteResult, ToSql};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::fmt::Display;
 
 pub const MIN_PRICE: f32 = 0.0;
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq, PartialOrd, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
 pub struct Price {
     price: f32,
 }
 
+impl<'de> Deserialize<'de> for Price {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Price::new(json::deserialize_newtype(deserializer, "price")?).map_err(json::range_error)
+    }
+}
+
 impl Display for Price {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.price)
@@ -35,6 +41,12 @@ impl ToSql for Price {
     }
 }
 
+impl Serialize for Price {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        json::serialize_newtype(serializer, "Price", "price", &self.price)
+    }
+}
+
 impl Price {
     pub fn new(price: f32) -> Result<Self, FgdbRangeError> {
         if price < MIN_PRICE {
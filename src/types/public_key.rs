@@ -0,0 +1,77 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::util::errors::FgdbParseError;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+// Length in bytes of an Ed25519 public key.
+const PUBLIC_KEY_LEN: usize = 32;
+
+/// An Ed25519 public key used to verify repository bundles.  Keys are written using standard, padded base64, e.g.,
+/// in the public_keys setting of fgdb.toml.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PublicKey([u8; PUBLIC_KEY_LEN]);
+
+impl Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", STANDARD.encode(self.0))
+    }
+}
+
+impl From<PublicKey> for String {
+    fn from(key: PublicKey) -> String {
+        key.to_string()
+    }
+}
+
+impl FromStr for PublicKey {
+    type Err = FgdbParseError;
+    fn from_str(s: &str) -> Result<PublicKey, FgdbParseError> {
+        let bytes = STANDARD.decode(s.trim()).map_err(|_| FgdbParseError::InvalidPublicKey(s.to_string()))?;
+        let bytes = <[u8; PUBLIC_KEY_LEN]>::try_from(bytes).map_err(|_| FgdbParseError::InvalidPublicKey(s.to_string()))?;
+        Ok(PublicKey(bytes))
+    }
+}
+
+impl TryFrom<String> for PublicKey {
+    type Error = FgdbParseError;
+    fn try_from(s: String) -> Result<PublicKey, FgdbParseError> {
+        PublicKey::from_str(&s)
+    }
+}
+
+impl PublicKey {
+    pub fn from_bytes(bytes: [u8; PUBLIC_KEY_LEN]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; PUBLIC_KEY_LEN] {
+        &self.0
+    }
+
+    /// Returns true if signature is a valid Ed25519 signature of message made with the private key of this key.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        UnparsedPublicKey::new(&ED25519, &self.0).verify(message, signature).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_works() {
+        let key = PublicKey::from_bytes([7; PUBLIC_KEY_LEN]);
+        assert_eq!(key, PublicKey::from_str(&key.to_string()).unwrap());
+        assert!(matches!(PublicKey::from_str("AAAA"), Err(FgdbParseError::InvalidPublicKey(_))));
+        assert!(matches!(PublicKey::from_str("not base64!"), Err(FgdbParseError::InvalidPublicKey(_))));
+    }
+}
@@ -2,9 +2,13 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 2/18/2026.
 
+use crate::util::errors::FgdbParseError;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
+use rusqlite::{Result as RusqliteResult, ToSql};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Display;
+use std::str::FromStr;
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub enum Repository {
@@ -27,3 +31,26 @@ impl Display for Repository {
         )
     }
 }
+
+impl FromSql for Repository {
+    fn column_result(value: ValueRef) -> FromSqlResult<Self> {
+        Repository::from_str(value.as_str()?).map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+impl FromStr for Repository {
+    type Err = FgdbParseError;
+    fn from_str(s: &str) -> Result<Repository, FgdbParseError> {
+        match s {
+            "Local" => Ok(Repository::Local),
+            "Remote" => Ok(Repository::Remote),
+            _ => Err(FgdbParseError::InvalidRepository(s.to_string())),
+        }
+    }
+}
+
+impl ToSql for Repository {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        Ok(self.to_string().into())
+    }
+}
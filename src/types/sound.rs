@@ -5,7 +5,6 @@
 use crate::types::sound_volume::SoundVolume;
 use crate::types::stock_sound::StockSound;
 use crate::util::errors::FgdbFromSqlError;
-use anyhow::Result;
 use rusqlite::Error as RusqliteError;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
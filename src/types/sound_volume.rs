@@ -3,10 +3,10 @@
 // Created by Hankinsohl on 2/6/2026.
 
 use crate::util::errors::FgdbRangeError;
-use anyhow::Result;
+use crate::util::json;
 use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
 use rusqlite::{Result as RusqliteResult, ToSql};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::fmt::Display;
 use std::ops::RangeInclusive;
@@ -15,11 +15,17 @@ pub const MIN_SOUND_VOLUME: u16 = 0;
 pub const MAX_SOUND_VOLUME: u16 = 300;
 pub const SOUND_VOLUME_RANGE: RangeInclusive<u16> = MIN_SOUND_VOLUME..=MAX_SOUND_VOLUME;
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct SoundVolume {
     volume: u16,
 }
 
+impl<'de> Deserialize<'de> for SoundVolume {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SoundVolume::new(json::deserialize_newtype(deserializer, "volume")?).map_err(json::range_error)
+    }
+}
+
 impl Display for SoundVolume {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.volume)
@@ -38,6 +44,12 @@ impl ToSql for SoundVolume {
     }
 }
 
+impl Serialize for SoundVolume {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        json::serialize_newtype(serializer, "SoundVolume", "volume", &self.volume)
+    }
+}
+
 impl SoundVolume {
     pub fn new(volume: u16) -> Result<Self, FgdbRangeError> {
         if !SOUND_VOLUME_RANGE.contains(&volume) {
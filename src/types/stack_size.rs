@@ -3,20 +3,26 @@
 // Created by Hankinsohl on 2/7/2026.
 
 use crate::util::errors::FgdbRangeError;
-use anyhow::Result;
+use crate::util::json;
 use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
 use rusqlite::{Result as RusqliteResult, ToSql};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::fmt::Display;
 
 pub const MIN_STACK_SIZE: u32 = 1;
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct StackSize {
     size: u32,
 }
 
+impl<'de> Deserialize<'de> for StackSize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        StackSize::new(json::deserialize_newtype(deserializer, "size")?).map_err(json::range_error)
+    }
+}
+
 impl Display for StackSize {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.size)
@@ -35,6 +41,12 @@ impl ToSql for StackSize {
     }
 }
 
+impl Serialize for StackSize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        json::serialize_newtype(serializer, "StackSize", "size", &self.size)
+    }
+}
+
 impl StackSize {
     pub fn new(size: u32) -> Result<Self, FgdbRangeError> {
         if size < MIN_STACK_SIZE {
@@ -51,7 +63,7 @@ impl StackSize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::util::errors::FgdbRangeError;
+    use crate::util::errors::{FgdbJsonError, FgdbRangeError};
 
     #[test]
     fn test_stack_size_new_works_with_valid_size() {
@@ -64,4 +76,10 @@ mod tests {
         let result = StackSize::new(0);
         assert!(matches!(result, Err(FgdbRangeError::StackSize())));
     }
+
+    #[test]
+    fn test_stack_size_deserialize_generates_error_with_invalid_size() {
+        let result = json::from_str::<StackSize>(r#"{"size": 0}"#);
+        assert!(matches!(result, Err(FgdbJsonError::Range(_, FgdbRangeError::StackSize()))));
+    }
 }
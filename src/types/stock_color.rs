@@ -3,7 +3,7 @@
 // Created by Hankinsohl on 2/3/2026.
 
 use crate::util::errors::FgdbParseError;
-use anyhow::Result;
+use rgb::RGB8 as Rgb8;
 use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
 use rusqlite::Result as RusqliteResult;
 use rusqlite::ToSql;
@@ -81,6 +81,26 @@ impl ToSql for StockColor {
     }
 }
 
+impl StockColor {
+    /// Returns the RGB value used in-game for the stock color.  GGG does not publish these values; they were sampled
+    /// from in-game screenshots of drop text and are approximate.
+    pub fn rgb(&self) -> Rgb8 {
+        match self {
+            StockColor::Blue => Rgb8::new(57, 133, 255),
+            StockColor::Brown => Rgb8::new(165, 104, 46),
+            StockColor::Cyan => Rgb8::new(0, 255, 255),
+            StockColor::Green => Rgb8::new(34, 255, 34),
+            StockColor::Grey => Rgb8::new(128, 128, 128),
+            StockColor::Orange => Rgb8::new(255, 140, 0),
+            StockColor::Pink => Rgb8::new(255, 105, 180),
+            StockColor::Purple => Rgb8::new(163, 73, 220),
+            StockColor::Red => Rgb8::new(255, 34, 34),
+            StockColor::White => Rgb8::new(255, 255, 255),
+            StockColor::Yellow => Rgb8::new(255, 255, 0),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
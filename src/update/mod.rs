@@ -3,4 +3,7 @@
 // Created by Hankinsohl on 2/12/2026.
 
 pub mod policy;
+pub mod price_refresher;
+pub mod progress;
+pub mod update_log;
 pub mod updater;
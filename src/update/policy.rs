@@ -2,6 +2,14 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 2/12/2026.
 
+use crate::util::errors::FgdbParseError;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
+use rusqlite::{Result as RusqliteResult, ToSql};
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Policy {
     // Skip the update
     Skip,
@@ -12,3 +20,41 @@ pub enum Policy {
     // Remove all existing data and replace with up-to-date data
     Force,
 }
+
+impl Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Policy::Skip => "Skip",
+                Policy::Auto => "Auto",
+                Policy::Force => "Force",
+            }
+        )
+    }
+}
+
+impl FromSql for Policy {
+    fn column_result(value: ValueRef) -> FromSqlResult<Self> {
+        Policy::from_str(value.as_str()?).map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+impl FromStr for Policy {
+    type Err = FgdbParseError;
+    fn from_str(s: &str) -> Result<Policy, FgdbParseError> {
+        match s {
+            "Skip" => Ok(Policy::Skip),
+            "Auto" => Ok(Policy::Auto),
+            "Force" => Ok(Policy::Force),
+            _ => Err(FgdbParseError::InvalidPolicy(s.to_string())),
+        }
+    }
+}
+
+impl ToSql for Policy {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        Ok(self.to_string().into())
+    }
+}
@@ -0,0 +1,452 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::concurrency::cancellation_token::CancellationToken;
+use crate::db::conn::Conn;
+use crate::db::tables::names::{ASYNC_PRICES, EXCHANGE_PRICES, PRICE_REFRESHES};
+use crate::db::tx::Tx;
+use crate::update::policy::Policy;
+use crate::update::updater::Updater;
+use crate::util::consts;
+use crate::util::errors::FgdbError;
+use rand::Rng;
+use rusqlite::{OptionalExtension, params};
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Display;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A table of prices which PriceRefresher keeps current.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PriceSource {
+    AsyncPrices,
+    ExchangePrices,
+}
+
+impl Display for PriceSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.table_name())
+    }
+}
+
+impl PriceSource {
+    pub fn table_name(&self) -> &'static str {
+        match self {
+            PriceSource::AsyncPrices => ASYNC_PRICES,
+            PriceSource::ExchangePrices => EXCHANGE_PRICES,
+        }
+    }
+}
+
+/// Sent to subscribers of a running PriceRefresher each time a refresh is attempted.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PriceRefreshEvent {
+    // New prices for sources were imported at refreshed_at.
+    Refreshed { sources: Vec<PriceSource>, refreshed_at: SystemTime },
+
+    // Refreshing sources failed; the previous prices remain in the database.
+    Failed { sources: Vec<PriceSource>, error: String },
+}
+
+/// Refreshes prices on a schedule.  A refresh in which a source is due checks whether the repository has changed, which
+/// costs a single conditional request for a remote repository, and if it has, updates the database from it as
+/// Policy::Force does.  A source is due once its minimum interval has passed since it was last refreshed.  The time of
+/// each source's last refresh is kept in the database, so the minimum interval is respected across restarts and by
+/// every process sharing the database.
+#[derive(Clone, Debug)]
+pub struct PriceRefresher {
+    updater: Updater,
+    interval: Duration,
+    jitter: Duration,
+    sources: Vec<PriceSource>,
+    min_intervals: HashMap<PriceSource, Duration>,
+}
+
+impl PriceRefresher {
+    /// Returns a builder for a refresher which updates the database of updater.  By default only async prices are
+    /// refreshed, once an hour plus up to five minutes of jitter, and no source more often than every 15 minutes.
+    pub fn builder(updater: Updater) -> PriceRefresherBuilder {
+        PriceRefresherBuilder {
+            refresher: PriceRefresher {
+                updater,
+                interval: Duration::from_secs(consts::PRICE_REFRESH_INTERVAL_SECS),
+                jitter: Duration::from_secs(consts::PRICE_REFRESH_JITTER_SECS),
+                sources: vec![PriceSource::AsyncPrices],
+                min_intervals: HashMap::new(),
+            },
+        }
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn jitter(&self) -> Duration {
+        self.jitter
+    }
+
+    /// Returns the minimum time between refreshes of source.
+    pub fn min_interval(&self, source: PriceSource) -> Duration {
+        self.min_intervals
+            .get(&source)
+            .copied()
+            .unwrap_or(Duration::from_secs(consts::PRICE_REFRESH_MIN_INTERVAL_SECS))
+    }
+
+    pub fn sources(&self) -> &[PriceSource] {
+        &self.sources
+    }
+
+    /// Returns the time source was last refreshed, or None if it has never been refreshed.
+    pub fn last_refresh(tx: &mut Tx, source: PriceSource) -> Result<Option<SystemTime>, FgdbError> {
+        let secs: Option<i64> = tx
+            .query_row(
+                "SELECT refreshed_at FROM price_refreshes WHERE source = ?1",
+                params![source.table_name()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(secs.map(|secs| UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64)))
+    }
+
+    /// Returns the sources which are due for a refresh at now.
+    pub fn due_sources(&self, tx: &mut Tx, now: SystemTime) -> Result<Vec<PriceSource>, FgdbError> {
+        let mut due = Vec::new();
+        for source in &self.sources {
+            let is_due = match PriceRefresher::last_refresh(tx, *source)? {
+                Some(last) => now.duration_since(last).is_ok_and(|elapsed| elapsed >= self.min_interval(*source)),
+                None => true,
+            };
+            if is_due {
+                due.push(*source);
+            }
+        }
+        Ok(due)
+    }
+
+    /// Refreshes the sources which are due, returning the sources whose prices were imported.  Nothing is downloaded if
+    /// no source is due or if the repository is unchanged.
+    pub fn refresh(&self, token: &CancellationToken) -> Result<Vec<PriceSource>, FgdbError> {
+        let due = self.due_now()?;
+        let is_refreshed = self.refresh_sources(&due, token)?;
+        Ok(if is_refreshed { due } else { Vec::new() })
+    }
+
+    // Returns the sources which are due now.
+    fn due_now(&self) -> Result<Vec<PriceSource>, FgdbError> {
+        let mut conn = Conn::with_paths(&self.updater.paths)?;
+        let mut tx = conn.create_tx()?;
+        // Databases created before price_refreshes was part of the schema gain the table here.
+        create_refresh_table(&mut tx)?;
+        self.due_sources(&mut tx, SystemTime::now())
+    }
+
+    // Refreshes sources and records the time of the refresh, returning true if new prices were imported.  Nothing is
+    // downloaded if sources is empty or if the cache is current, in which case the database already holds its prices.
+    fn refresh_sources(&self, sources: &[PriceSource], token: &CancellationToken) -> Result<bool, FgdbError> {
+        if sources.is_empty() {
+            return Ok(false);
+        }
+
+        token.check()?;
+        let is_refreshed = !self.updater.is_cache_current()?;
+        if is_refreshed {
+            self.updater.update_cancellable(Policy::Force, token)?;
+        }
+
+        let mut conn = Conn::with_paths(&self.updater.paths)?;
+        let mut tx = conn.create_tx()?;
+        let refreshed_at = SystemTime::now();
+        for source in sources {
+            record_refresh(&mut tx, *source, refreshed_at)?;
+        }
+        Ok(is_refreshed)
+    }
+
+    /// Starts refreshing on a background thread.  The first refresh is attempted immediately; thereafter the thread
+    /// waits for the interval plus a random delay of up to the jitter between attempts.  The thread runs until the
+    /// returned handle is stopped or dropped.
+    pub fn start(self) -> PriceRefresherHandle {
+        let (commands, receiver) = mpsc::channel();
+        let subscribers: Arc<Mutex<Vec<Sender<PriceRefreshEvent>>>> = Arc::new(Mutex::new(Vec::new()));
+        let token = CancellationToken::new();
+        let thread = {
+            let subscribers = Arc::clone(&subscribers);
+            let token = token.clone();
+            thread::spawn(move || self.run(&receiver, &subscribers, &token))
+        };
+        PriceRefresherHandle {
+            commands,
+            subscribers,
+            token,
+            thread: Some(thread),
+        }
+    }
+
+    fn run(&self, commands: &Receiver<Command>, subscribers: &Mutex<Vec<Sender<PriceRefreshEvent>>>, token: &CancellationToken) {
+        loop {
+            let (sources, result) = match self.due_now() {
+                Ok(due) => {
+                    let result = self.refresh_sources(&due, token);
+                    (due, result)
+                }
+                // If the sources due cannot be determined, the refresh of every source has failed.
+                Err(e) => (self.sources.clone(), Err(e)),
+            };
+            let event = match result {
+                Ok(false) => None,
+                Ok(true) => Some(PriceRefreshEvent::Refreshed {
+                    sources,
+                    refreshed_at: SystemTime::now(),
+                }),
+                Err(FgdbError::Cancelled) => return,
+                Err(e) => Some(PriceRefreshEvent::Failed { sources, error: e.to_string() }),
+            };
+            if let Some(event) = event {
+                // Subscribers whose receivers have been dropped are removed.
+                subscribers
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+            }
+
+            match commands.recv_timeout(self.next_delay()) {
+                Ok(Command::Refresh) | Err(RecvTimeoutError::Timeout) => {}
+                Ok(Command::Stop) | Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+    }
+
+    fn next_delay(&self) -> Duration {
+        let jitter = rand::rng().random_range(0..=self.jitter.as_millis() as u64);
+        self.interval + Duration::from_millis(jitter)
+    }
+}
+
+// Builder for PriceRefresher.
+#[derive(Debug)]
+pub struct PriceRefresherBuilder {
+    refresher: PriceRefresher,
+}
+
+impl PriceRefresherBuilder {
+    pub fn build(self) -> PriceRefresher {
+        self.refresher
+    }
+
+    pub fn interval(mut self, interval: Duration) -> PriceRefresherBuilder {
+        self.refresher.interval = interval;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: Duration) -> PriceRefresherBuilder {
+        self.refresher.jitter = jitter;
+        self
+    }
+
+    pub fn min_interval(mut self, source: PriceSource, min_interval: Duration) -> PriceRefresherBuilder {
+        self.refresher.min_intervals.insert(source, min_interval);
+        self
+    }
+
+    /// Sets the sources to refresh, replacing the default of async prices only.
+    pub fn sources(mut self, sources: impl IntoIterator<Item = PriceSource>) -> PriceRefresherBuilder {
+        self.refresher.sources = sources.into_iter().collect();
+        self
+    }
+}
+
+enum Command {
+    Refresh,
+    Stop,
+}
+
+/// Controls a PriceRefresher running on a background thread.  Dropping the handle stops the refresher.
+pub struct PriceRefresherHandle {
+    commands: Sender<Command>,
+    subscribers: Arc<Mutex<Vec<Sender<PriceRefreshEvent>>>>,
+    token: CancellationToken,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PriceRefresherHandle {
+    /// Returns a receiver for the events of every later refresh attempt which imports new prices or fails.
+    pub fn subscribe(&self) -> Receiver<PriceRefreshEvent> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.lock().unwrap_or_else(PoisonError::into_inner).push(sender);
+        receiver
+    }
+
+    /// Attempts a refresh without waiting for the interval to pass.  Sources which are not yet due are still skipped.
+    pub fn refresh_now(&self) {
+        let _ = self.commands.send(Command::Refresh);
+    }
+
+    /// Stops the refresher, cancelling any refresh in progress, and waits for its thread to finish.
+    pub fn stop(mut self) {
+        self.stop_impl();
+    }
+
+    fn stop_impl(&mut self) {
+        self.token.cancel();
+        let _ = self.commands.send(Command::Stop);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for PriceRefresherHandle {
+    fn drop(&mut self) {
+        self.stop_impl();
+    }
+}
+
+/// Creates the price_refreshes table, which records when each source was last refreshed, if it does not exist.  The
+/// table is not one of TABLES and so is kept when the database is rebuilt.
+pub(crate) fn create_refresh_table(tx: &mut Tx) -> Result<(), FgdbError> {
+    tx.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {}
+                (
+                    source          TEXT    NOT NULL PRIMARY KEY,
+
+                    -- Seconds since the Unix epoch.
+                    refreshed_at    INTEGER NOT NULL
+                ) STRICT",
+            PRICE_REFRESHES
+        ),
+        (),
+    )?;
+    Ok(())
+}
+
+fn record_refresh(tx: &mut Tx, source: PriceSource, refreshed_at: SystemTime) -> Result<(), FgdbError> {
+    create_refresh_table(tx)?;
+    let secs = refreshed_at.duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or(0) as i64;
+    tx.execute(
+        "INSERT INTO price_refreshes (source, refreshed_at) VALUES (?1, ?2)
+            ON CONFLICT (source) DO UPDATE SET refreshed_at = excluded.refreshed_at",
+        params![source.table_name(), secs],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use crate::config::fgdb_config::get_config;
+    use crate::fs::dir::Dir;
+    use crate::fs::paths::Paths;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn refresher(env_guard: &EnvPoolGuard, root_path: &str) -> PriceRefresherBuilder {
+        PriceRefresher::builder(Updater::with_paths(PathBuf::from(root_path), Paths::new(env_guard.env)))
+    }
+
+    #[test]
+    fn test_last_refresh_is_persisted_and_min_interval_respected() {
+        let env_guard = EnvPoolGuard::new();
+        let refresher = refresher(&env_guard, "missing")
+            .sources([PriceSource::AsyncPrices, PriceSource::ExchangePrices])
+            .min_interval(PriceSource::ExchangePrices, Duration::from_secs(60))
+            .build();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        {
+            let mut tx = conn.create_tx().unwrap();
+            assert_eq!(None, PriceRefresher::last_refresh(&mut tx, PriceSource::AsyncPrices).unwrap());
+            assert_eq!(
+                vec![PriceSource::AsyncPrices, PriceSource::ExchangePrices],
+                refresher.due_sources(&mut tx, now).unwrap()
+            );
+            record_refresh(&mut tx, PriceSource::AsyncPrices, now).unwrap();
+            record_refresh(&mut tx, PriceSource::ExchangePrices, now).unwrap();
+        }
+
+        let mut tx = conn.create_test_tx().unwrap();
+        assert_eq!(Some(now), PriceRefresher::last_refresh(&mut tx, PriceSource::AsyncPrices).unwrap());
+        assert!(refresher.due_sources(&mut tx, now + Duration::from_secs(59)).unwrap().is_empty());
+        assert_eq!(
+            vec![PriceSource::ExchangePrices],
+            refresher.due_sources(&mut tx, now + Duration::from_secs(60)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_last_refresh_works_on_read_only_connection() {
+        let env_guard = EnvPoolGuard::new();
+        let mut conn = Conn::read_only(&Paths::new(env_guard.env)).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        assert_eq!(None, PriceRefresher::last_refresh(&mut tx, PriceSource::AsyncPrices).unwrap());
+    }
+
+    #[test]
+    fn test_subscribers_are_notified_of_failed_refresh() {
+        let env_guard = EnvPoolGuard::new();
+        {
+            // Exchange prices are not due, so only async prices are attempted.
+            let mut conn = Conn::new(env_guard.env).unwrap();
+            record_refresh(&mut conn.create_tx().unwrap(), PriceSource::ExchangePrices, SystemTime::now()).unwrap();
+        }
+        let handle = refresher(&env_guard, "missing")
+            .sources([PriceSource::AsyncPrices, PriceSource::ExchangePrices])
+            .interval(Duration::from_secs(3600))
+            .jitter(Duration::ZERO)
+            .build()
+            .start();
+        let events = handle.subscribe();
+        handle.refresh_now();
+        match events.recv_timeout(Duration::from_secs(60)).unwrap() {
+            PriceRefreshEvent::Failed { sources, .. } => assert_eq!(vec![PriceSource::AsyncPrices], sources),
+            event => panic!("unexpected event {:?}", event),
+        }
+        handle.stop();
+        let mut conn = Conn::new(env_guard.env).unwrap();
+        assert_eq!(
+            None,
+            PriceRefresher::last_refresh(&mut conn.create_test_tx().unwrap(), PriceSource::AsyncPrices).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_unchanged_repository_downloads_nothing() {
+        let env_guard = EnvPoolGuard::new();
+        let root_path = env_guard.out_dir("test_unchanged_repository_downloads_nothing");
+        let paths = Paths::with_root(&root_path.join("data"), get_config().game_variant, env_guard.env);
+        paths.create_dirs([Dir::CacheZip, Dir::CacheTimestamp, Dir::CacheJson].iter()).unwrap();
+        // The repository has no bundle, so any attempt to download it fails.
+        let repository_path = root_path.join(paths.game_variant().to_string());
+        fs::create_dir_all(repository_path.join(consts::REPOSITORY_TIMESTAMP_DIR)).unwrap();
+        fs::write(repository_path.join(consts::REPOSITORY_TIMESTAMP_DIR).join(consts::TIMESTAMP_FILE_NAME), "{}").unwrap();
+        fs::write(paths.lookup(Dir::CacheTimestamp).join(consts::TIMESTAMP_FILE_NAME), "{}").unwrap();
+        let refresher = PriceRefresher::builder(Updater::with_paths(root_path, paths.clone()).allow_unsigned(true)).build();
+
+        assert!(refresher.refresh(&CancellationToken::new()).unwrap().is_empty());
+        let mut conn = Conn::with_paths(&paths).unwrap();
+        assert!(
+            PriceRefresher::last_refresh(&mut conn.create_test_tx().unwrap(), PriceSource::AsyncPrices)
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_next_delay_is_within_jitter() {
+        let env_guard = EnvPoolGuard::new();
+        let refresher = refresher(&env_guard, "missing")
+            .interval(Duration::from_secs(10))
+            .jitter(Duration::from_secs(2))
+            .build();
+        for _ in 0..100 {
+            let delay = refresher.next_delay();
+            assert!(delay >= Duration::from_secs(10) && delay <= Duration::from_secs(12));
+        }
+    }
+}
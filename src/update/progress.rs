@@ -0,0 +1,46 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+/// A phase of an update, reported to a ProgressObserver as the update proceeds.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Progress {
+    // Checking whether the cache is current.
+    Checking,
+
+    // Downloading the repository.  bytes of total bytes have been downloaded.
+    Downloading { bytes: u64, total: u64 },
+
+    // Extracting the downloaded zip file into the cache.
+    Extracting,
+
+    // Importing table.  rows of total rows have been imported.
+    Importing { table: String, rows: usize, total: usize },
+
+    // The repository could not be reached, so the current cache and database were kept.  error gives the reason.
+    Stale { error: String },
+}
+
+/// Receives progress reports from a long-running operation such as Updater::update_with_progress.  Reports are made on
+/// the thread performing the operation, so observers should return promptly, e.g., by forwarding the report to a UI
+/// thread.  Any Fn(&Progress) closure which is Send and Sync is an observer.
+pub trait ProgressObserver: Send + Sync {
+    fn on_progress(&self, progress: &Progress);
+}
+
+impl<F> ProgressObserver for F
+where
+    F: Fn(&Progress) + Send + Sync,
+{
+    fn on_progress(&self, progress: &Progress) {
+        self(progress)
+    }
+}
+
+/// An observer which ignores every report.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoProgress;
+
+impl ProgressObserver for NoProgress {
+    fn on_progress(&self, _progress: &Progress) {}
+}
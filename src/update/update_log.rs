@@ -0,0 +1,278 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::db::database::TABLES;
+use crate::db::tables::names::{UPDATE_LOG, UPDATE_LOG_ROW_COUNTS};
+use crate::db::tx::Tx;
+use crate::types::repository::Repository;
+use crate::update::policy::Policy;
+use crate::util::errors::{FgdbError, FgdbParseError};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
+use rusqlite::{params, Error as RusqliteError, Result as RusqliteResult, ToSql};
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The outcome of an update.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpdateOutcome {
+    // The database was rebuilt.
+    Updated,
+
+    // The database was left unchanged, either because of the policy or because the cache was current.
+    Skipped,
+
+    // The repository could not be reached and the current data was kept; the error is recorded in the log entry.
+    Stale,
+
+    // The update failed or was cancelled; the error is recorded in the log entry.
+    Failed,
+}
+
+impl Display for UpdateOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                UpdateOutcome::Updated => "Updated",
+                UpdateOutcome::Skipped => "Skipped",
+                UpdateOutcome::Stale => "Stale",
+                UpdateOutcome::Failed => "Failed",
+            }
+        )
+    }
+}
+
+impl FromSql for UpdateOutcome {
+    fn column_result(value: ValueRef) -> FromSqlResult<Self> {
+        UpdateOutcome::from_str(value.as_str()?).map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+impl FromStr for UpdateOutcome {
+    type Err = FgdbParseError;
+    fn from_str(s: &str) -> Result<UpdateOutcome, FgdbParseError> {
+        match s {
+            "Updated" => Ok(UpdateOutcome::Updated),
+            "Skipped" => Ok(UpdateOutcome::Skipped),
+            "Stale" => Ok(UpdateOutcome::Stale),
+            "Failed" => Ok(UpdateOutcome::Failed),
+            _ => Err(FgdbParseError::InvalidUpdateOutcome(s.to_string())),
+        }
+    }
+}
+
+impl ToSql for UpdateOutcome {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        Ok(self.to_string().into())
+    }
+}
+
+/// The number of rows in a table before and after an update.  A count is None if the table could not be counted, e.g.,
+/// because it did not yet exist.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TableRowCounts {
+    pub table: String,
+    pub before: Option<usize>,
+    pub after: Option<usize>,
+}
+
+/// A record of one call to Updater::update.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateLogEntry {
+    // Assigned when the entry is recorded.
+    pub update_id: i64,
+
+    // Recorded to the second.
+    pub started_at: SystemTime,
+
+    // Recorded to the millisecond.
+    pub duration: Duration,
+
+    pub policy: Policy,
+    pub repository: Repository,
+
+    // The path or URL of the repository.
+    pub location: String,
+
+    // The version of the cached bundle before and after the update, e.g., the repository timestamp or the ETag of the
+    // bundle, if there was one.  See Repository::cached_version.
+    pub old_timestamp: Option<String>,
+    pub new_timestamp: Option<String>,
+
+    pub row_counts: Vec<TableRowCounts>,
+    pub outcome: UpdateOutcome,
+    pub error: Option<String>,
+}
+
+/// The update_log table, which records every call to Updater::update, and its child table update_log_row_counts.  The
+/// tables are created on first use and are not dropped when the database is rebuilt.
+pub struct UpdateLog;
+
+impl UpdateLog {
+    pub fn create(tx: &mut Tx) -> Result<(), FgdbError> {
+        tx.execute(
+            &format!(
+                "CREATE TABLE IF NOT EXISTS {}
+                    (
+                        update_id       INTEGER NOT NULL PRIMARY KEY,
+
+                        -- Seconds since the Unix epoch.
+                        started_at      INTEGER NOT NULL,
+                        duration_ms     INTEGER NOT NULL               CHECK (duration_ms >= 0),
+                        policy          TEXT    NOT NULL,
+                        repository      TEXT    NOT NULL,
+                        location        TEXT    NOT NULL,
+                        old_timestamp   TEXT,
+                        new_timestamp   TEXT,
+                        outcome         TEXT    NOT NULL,
+                        error           TEXT
+                    ) STRICT",
+                UPDATE_LOG
+            ),
+            (),
+        )?;
+        tx.execute(
+            &format!(
+                "CREATE TABLE IF NOT EXISTS {}
+                    (
+                        update_id       INTEGER NOT NULL,
+                        table_name      TEXT    NOT NULL,
+                        rows_before     INTEGER,
+                        rows_after      INTEGER,
+                        PRIMARY KEY (update_id, table_name),
+                        FOREIGN KEY (update_id) REFERENCES update_log (update_id) ON DELETE CASCADE
+                    ) STRICT",
+                UPDATE_LOG_ROW_COUNTS
+            ),
+            (),
+        )?;
+        Ok(())
+    }
+
+    /// Records entry, returning the update_id assigned to it.  The update_id of entry is ignored.
+    pub fn record(tx: &mut Tx, entry: &UpdateLogEntry) -> Result<i64, FgdbError> {
+        UpdateLog::create(tx)?;
+        let started_at = entry.started_at.duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or(0) as i64;
+        tx.execute(
+            "INSERT INTO update_log
+                (started_at, duration_ms, policy, repository, location, old_timestamp, new_timestamp, outcome, error)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                started_at,
+                entry.duration.as_millis() as i64,
+                entry.policy,
+                entry.repository,
+                entry.location,
+                entry.old_timestamp,
+                entry.new_timestamp,
+                entry.outcome,
+                entry.error
+            ],
+        )?;
+        let update_id = tx.last_insert_rowid();
+        let mut stmt =
+            tx.prepare_cached("INSERT INTO update_log_row_counts (update_id, table_name, rows_before, rows_after) VALUES (?1, ?2, ?3, ?4)")?;
+        for counts in &entry.row_counts {
+            stmt.execute(params![update_id, counts.table, counts.before.map(|n| n as i64), counts.after.map(|n| n as i64)])?;
+        }
+        Ok(update_id)
+    }
+
+    /// Returns up to limit entries, most recent first.
+    pub fn recent(tx: &mut Tx, limit: usize) -> Result<Vec<UpdateLogEntry>, FgdbError> {
+        UpdateLog::create(tx)?;
+        let mut stmt = tx.prepare(
+            "SELECT update_id, started_at, duration_ms, policy, repository, location, old_timestamp, new_timestamp, outcome, error
+                FROM update_log ORDER BY update_id DESC LIMIT ?1",
+        )?;
+        let mut entries = stmt
+            .query_map(params![limit as i64], |row| {
+                Ok(UpdateLogEntry {
+                    update_id: row.get(0)?,
+                    started_at: UNIX_EPOCH + Duration::from_secs(row.get::<usize, i64>(1)?.max(0) as u64),
+                    duration: Duration::from_millis(row.get::<usize, i64>(2)?.max(0) as u64),
+                    policy: row.get(3)?,
+                    repository: row.get(4)?,
+                    location: row.get(5)?,
+                    old_timestamp: row.get(6)?,
+                    new_timestamp: row.get(7)?,
+                    row_counts: Vec::new(),
+                    outcome: row.get(8)?,
+                    error: row.get(9)?,
+                })
+            })?
+            .collect::<Result<Vec<UpdateLogEntry>, RusqliteError>>()?;
+
+        let mut stmt = tx.prepare_cached(
+            "SELECT table_name, rows_before, rows_after FROM update_log_row_counts WHERE update_id = ?1 ORDER BY rowid",
+        )?;
+        for entry in &mut entries {
+            entry.row_counts = stmt
+                .query_map(params![entry.update_id], |row| {
+                    Ok(TableRowCounts {
+                        table: row.get(0)?,
+                        before: row.get::<usize, Option<i64>>(1)?.map(|n| n as usize),
+                        after: row.get::<usize, Option<i64>>(2)?.map(|n| n as usize),
+                    })
+                })?
+                .collect::<Result<Vec<TableRowCounts>, RusqliteError>>()?;
+        }
+        Ok(entries)
+    }
+
+    /// Returns the number of rows in each table, or None for a table which cannot be counted.
+    pub fn row_counts(tx: &mut Tx) -> Vec<(String, Option<usize>)> {
+        TABLES.iter().map(|table| (table.name().to_string(), table.count(tx).ok())).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::conn::Conn;
+    use crate::types::game_variant::GameVariant;
+
+    #[test]
+    fn test_record_and_recent_work() {
+        let mut conn = Conn::in_memory(GameVariant::Poe1).unwrap();
+        let mut tx = conn.create_test_tx().unwrap();
+        let mut entry = UpdateLogEntry {
+            update_id: 0,
+            started_at: UNIX_EPOCH + Duration::from_secs(1_000_000),
+            duration: Duration::from_millis(1500),
+            policy: Policy::Force,
+            repository: Repository::Local,
+            location: "repository/Poe1".to_string(),
+            old_timestamp: None,
+            new_timestamp: Some("{}".to_string()),
+            row_counts: vec![TableRowCounts {
+                table: "classes".to_string(),
+                before: None,
+                after: Some(7),
+            }],
+            outcome: UpdateOutcome::Updated,
+            error: None,
+        };
+        let first = UpdateLog::record(&mut tx, &entry).unwrap();
+        entry.outcome = UpdateOutcome::Failed;
+        entry.error = Some("Cancelled.".to_string());
+        entry.row_counts.clear();
+        let second = UpdateLog::record(&mut tx, &entry).unwrap();
+
+        let entries = UpdateLog::recent(&mut tx, 10).unwrap();
+        assert_eq!(vec![second, first], entries.iter().map(|e| e.update_id).collect::<Vec<_>>());
+        assert_eq!(UpdateLogEntry { update_id: second, ..entry.clone() }, entries[0]);
+        assert_eq!(Some(7), entries[1].row_counts[0].after);
+        assert_eq!(1, UpdateLog::recent(&mut tx, 1).unwrap().len());
+    }
+
+    #[test]
+    fn test_update_outcome_from_str_works() {
+        assert_eq!(UpdateOutcome::Skipped, UpdateOutcome::from_str("Skipped").unwrap());
+        assert!(matches!(UpdateOutcome::from_str("Done"), Err(FgdbParseError::InvalidUpdateOutcome(_))));
+    }
+}
@@ -2,22 +2,47 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 2/12/2026.
 
-use crate::config::fgdb_config::get_config;
+#[cfg(feature = "async")]
+use crate::concurrency::blocking_task::{spawn_blocking, BlockingTask};
+use crate::concurrency::cancellation_token::CancellationToken;
+use crate::config::fgdb_config::{get_config, try_get_config, FgdbConfig};
 use crate::db::conn::Conn;
-use crate::db::database::Database;
+use crate::db::database::{Database, TABLES};
 use crate::fs::dir::Dir;
 use crate::fs::paths::Paths;
-use crate::repo::local_repository::LocalRepository;
-use crate::repo::repository::Repository;
+use crate::repo::bundle::{extract_bundle, BundleLimits};
+use crate::repo::repository::{create_repository, Repository};
+use crate::repo::retry_policy::RetryPolicy;
+use crate::repo::signature;
 use crate::update::policy::Policy;
+use crate::types::repository::Repository as RepositoryType;
+use crate::update::progress::{NoProgress, Progress, ProgressObserver};
+use crate::update::update_log::{TableRowCounts, UpdateLog, UpdateLogEntry, UpdateOutcome};
+use crate::util::consts;
 use crate::util::env::Env;
 use crate::GameVariant;
-use anyhow::{Error, Result};
-use zip_extensions::zip_extract::zip_extract;
+use crate::util::errors::FgdbError;
+use std::path::PathBuf;
+use std::time::{Instant, SystemTime};
+#[cfg(feature = "async")]
+use std::sync::Arc;
 
+#[derive(Clone, Debug)]
 pub struct Updater {
     pub game_variant: GameVariant,
     pub paths: Paths,
+
+    // The configuration naming the repository.  If None, the global configuration is used.
+    config: Option<FgdbConfig>,
+
+    retry_policy: RetryPolicy,
+    bundle_limits: BundleLimits,
+
+    // If true, Policy::Auto keeps the current cache when the repository cannot be reached.
+    offline_fallback: bool,
+
+    // If true, bundles are imported without verification when no public keys are configured.
+    allow_unsigned: bool,
 }
 
 impl Default for Updater {
@@ -35,64 +60,393 @@ impl Updater {
         Self {
             game_variant,
             paths: Paths::create(game_variant, Env::Prod),
+            config: None,
+            retry_policy: RetryPolicy::default(),
+            bundle_limits: BundleLimits::default(),
+            offline_fallback: false,
+            allow_unsigned: false,
+        }
+    }
+
+    /// Creates an updater which reads the local repository at root_path and updates the cache and production database
+    /// located by paths.  Neither the global configuration nor the data root is consulted.
+    pub fn with_paths(root_path: PathBuf, paths: Paths) -> Self {
+        let config = FgdbConfig::local(root_path, paths.game_variant());
+        Updater::with_config(config, paths)
+    }
+
+    /// Creates an updater which reads the repository named by config, local or remote, and updates the cache and
+    /// production database located by paths.
+    pub fn with_config(config: FgdbConfig, paths: Paths) -> Self {
+        Self {
+            game_variant: paths.game_variant(),
+            paths,
+            config: Some(config),
+            retry_policy: RetryPolicy::default(),
+            bundle_limits: BundleLimits::default(),
+            offline_fallback: false,
+            allow_unsigned: false,
         }
     }
 
+    /// Sets how checking and downloading the repository are retried.  By default, transient errors are retried with
+    /// exponential backoff; see RetryPolicy::default.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Updater {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the limits applied when the downloaded bundle is extracted.  See BundleLimits::default for the defaults.
+    pub fn bundle_limits(mut self, bundle_limits: BundleLimits) -> Updater {
+        self.bundle_limits = bundle_limits;
+        self
+    }
+
+    /// Sets whether Policy::Auto keeps the current cache and database when the repository cannot be reached.  With
+    /// offline fallback, such an update reports Progress::Stale, is logged as UpdateOutcome::Stale and returns false
+    /// rather than failing.  Offline fallback is off by default.
+    pub fn offline_fallback(mut self, offline_fallback: bool) -> Updater {
+        self.offline_fallback = offline_fallback;
+        self
+    }
+
+    /// Sets whether a bundle is imported without verifying its signature when the configuration sets no public keys.
+    /// Otherwise such an update fails with FgdbError::Config.  Unsigned bundles are refused by default; allow them only
+    /// in tests and development.
+    pub fn allow_unsigned(mut self, allow_unsigned: bool) -> Updater {
+        self.allow_unsigned = allow_unsigned;
+        self
+    }
+
     // Updates the database according to policy.  If update was performed, true is returned.
-    pub fn update(&self, policy: Policy) -> Result<bool, Error> {
+    pub fn update(&self, policy: Policy) -> Result<bool, FgdbError> {
+        self.update_cancellable(policy, &CancellationToken::new())
+    }
+
+    /// Updates the database according to policy, returning FgdbError::Cancelled if token is cancelled before the
+    /// update completes.  The database is rebuilt in a single transaction, so a cancelled update leaves it unchanged.
+    pub fn update_cancellable(&self, policy: Policy, token: &CancellationToken) -> Result<bool, FgdbError> {
+        self.update_with_progress(policy, &NoProgress, token)
+    }
+
+    /// Updates the database as update_cancellable does, reporting each phase of the update to observer: checking the
+    /// cache, downloading, extracting and importing each table.  token is checked between phases and between tables.
+    /// Every call, whatever its outcome, is recorded in the update log.
+    pub fn update_with_progress(&self, policy: Policy, observer: &dyn ProgressObserver, token: &CancellationToken) -> Result<bool, FgdbError> {
+        let started_at = SystemTime::now();
+        let start = Instant::now();
+        let old_timestamp = self.cached_version();
+        let counts_before = self.row_counts();
+        let result = self.update_unlogged(policy, observer, token);
+
+        let row_counts = counts_before
+            .into_iter()
+            .zip(self.row_counts())
+            .map(|((table, before), (_, after))| TableRowCounts { table, before, after })
+            .collect();
+        let (outcome, error) = match &result {
+            Ok((outcome, error)) => (*outcome, error.clone()),
+            Err(e) => (UpdateOutcome::Failed, Some(e.to_string())),
+        };
+        let (repository, location) = match self.repository() {
+            Ok(repo) => (repo.repository_type(), repo.location()),
+            Err(_) => (self.config().map(|config| config.repository).unwrap_or(RepositoryType::Local), String::new()),
+        };
+        let entry = UpdateLogEntry {
+            update_id: 0,
+            started_at,
+            duration: start.elapsed(),
+            policy,
+            repository,
+            location,
+            old_timestamp,
+            new_timestamp: self.cached_version(),
+            row_counts,
+            outcome,
+            error,
+        };
+        // The update log is a record only; failing to write it does not change the outcome of the update.
+        let _ = self.log_update(&entry);
+        result.map(|(outcome, _)| outcome == UpdateOutcome::Updated)
+    }
+
+    /// Returns up to limit entries from the update log, most recent first.
+    pub fn history(&self, limit: usize) -> Result<Vec<UpdateLogEntry>, FgdbError> {
+        let mut conn = Conn::with_paths(&self.paths)?;
+        let mut tx = conn.create_tx()?;
+        UpdateLog::recent(&mut tx, limit)
+    }
+
+    // Updates the database according to policy, returning the outcome and, for a stale outcome, the error which
+    // prevented the repository from being reached.
+    fn update_unlogged(&self, policy: Policy, observer: &dyn ProgressObserver, token: &CancellationToken) -> Result<(UpdateOutcome, Option<String>), FgdbError> {
+        token.check()?;
         match policy {
-            Policy::Skip => Ok(false),
+            Policy::Skip => Ok((UpdateOutcome::Skipped, None)),
             Policy::Auto => {
-                if !self.is_cache_current()? {
-                    self.update_impl()?;
-                    Ok(true)
-                } else {
-                    Ok(false)
+                observer.on_progress(&Progress::Checking);
+                let downloaded = self.retry_policy.run(token, || self.is_cache_current()).and_then(|is_current| {
+                    if is_current {
+                        Ok(false)
+                    } else {
+                        self.download(observer, token).map(|_| true)
+                    }
+                });
+                match downloaded {
+                    Ok(true) => {
+                        self.extract(observer, token)?;
+                        self.update_database(observer, token)?;
+                        self.repository()?.mark_cache_current()?;
+                        Ok((UpdateOutcome::Updated, None))
+                    }
+                    Ok(false) => Ok((UpdateOutcome::Skipped, None)),
+                    Err(e) if self.offline_fallback && e.is_unreachable() => {
+                        let error = e.to_string();
+                        observer.on_progress(&Progress::Stale { error: error.clone() });
+                        Ok((UpdateOutcome::Stale, Some(error)))
+                    }
+                    Err(e) => Err(e),
                 }
             }
             Policy::Force => {
-                self.update_impl()?;
-                Ok(true)
+                self.update_cache(observer, token)?;
+                self.update_database(observer, token)?;
+                self.repository()?.mark_cache_current()?;
+                Ok((UpdateOutcome::Updated, None))
             }
         }
     }
 
-    fn is_cache_current(&self) -> Result<bool, Error> {
-        // TODO - replace repository creation with call to factory function create_repository.
-        let repo = LocalRepository::create(get_config().root_path.clone(), get_config().game_variant);
-        repo.is_cache_current()
+    /// Updates the database according to policy on the blocking worker pool.  See update_with_progress.
+    #[cfg(feature = "async")]
+    pub fn update_async(&self, policy: Policy, observer: Arc<dyn ProgressObserver>, token: CancellationToken) -> BlockingTask<Result<bool, FgdbError>> {
+        let updater = self.clone();
+        spawn_blocking(move || updater.update_with_progress(policy, observer.as_ref(), &token))
     }
 
-    fn update_cache(&self) -> Result<(), Error> {
-        // TODO - replace repository creation with call to factory function create_repository.
-        let repo = LocalRepository::create(get_config().root_path.clone(), get_config().game_variant);
-        repo.download()?;
+    // Returns the version of the cached bundle, or None if it cannot be determined.
+    fn cached_version(&self) -> Option<String> {
+        self.repository().ok()?.cached_version()
+    }
 
-        let zip_file = repo.get_zip_path();
-        let cache_json_dir = self.paths.lookup(Dir::CacheJson).to_path_buf();
-        zip_extract(&zip_file, &cache_json_dir)?;
-        Ok(())
+    pub(crate) fn is_cache_current(&self) -> Result<bool, FgdbError> {
+        self.repository()?.is_cache_current()
+    }
+
+    fn config(&self) -> Result<&FgdbConfig, FgdbError> {
+        match &self.config {
+            Some(config) => Ok(config),
+            None => try_get_config(),
+        }
+    }
+
+    fn repository(&self) -> Result<Box<dyn Repository>, FgdbError> {
+        create_repository(self.config()?, self.paths.clone())
+    }
+
+    // Downloads the repository, retrying according to the retry policy.  Downloads resume where a failed attempt
+    // stopped.
+    fn download(&self, observer: &dyn ProgressObserver, token: &CancellationToken) -> Result<(), FgdbError> {
+        let repo = self.repository()?;
+        self.retry_policy.run(token, || repo.download_with_progress(observer, token))
+    }
+
+    fn extract(&self, observer: &dyn ProgressObserver, token: &CancellationToken) -> Result<(), FgdbError> {
+        token.check()?;
+        observer.on_progress(&Progress::Extracting);
+
+        let zip_file = self.paths.lookup(Dir::CacheZip).join(consts::REPOSITORY_ZIP_FILE_NAME);
+        let public_keys = &self.config()?.public_keys;
+        if !public_keys.is_empty() {
+            let signature_file = self.paths.lookup(Dir::CacheZip).join(consts::REPOSITORY_SIGNATURE_FILE_NAME);
+            signature::verify_bundle(&zip_file, &signature_file, public_keys)?;
+        } else if !self.allow_unsigned {
+            return Err(FgdbError::Config(
+                "public_keys is not set, so the bundle cannot be verified.  Set public_keys to the keys which sign the bundles.".to_string(),
+                None,
+            ));
+        }
+        extract_bundle(&zip_file, &self.paths.lookup(Dir::CacheJson), &self.bundle_limits, token)
     }
 
-    fn update_database(&self) -> Result<(), Error> {
+    fn update_cache(&self, observer: &dyn ProgressObserver, token: &CancellationToken) -> Result<(), FgdbError> {
+        self.download(observer, token)?;
+        self.extract(observer, token)
+    }
+
+    fn update_database(&self, observer: &dyn ProgressObserver, token: &CancellationToken) -> Result<(), FgdbError> {
         let db = Database::new()?;
-        let mut conn = Conn::create(self.game_variant, Env::Prod)?;
-        let mut tx = conn.create_tx()?;
+        let mut conn = Conn::with_paths(&self.paths)?;
+        // The transaction is committed only once every table has been imported.
+        let mut tx = conn.create_rollback_tx()?;
         db.drop_tables(&mut tx)?;
         db.create(&mut tx)?;
-        db.import(&mut tx)
+        db.import_dir_with_progress(&self.paths.lookup(Dir::CacheJson), &mut tx, observer, token)?;
+        token.check()?;
+        tx.commit()?;
+        Ok(())
     }
 
-    fn update_impl(&self) -> Result<(), Error> {
-        self.update_cache()?;
-        self.update_database()?;
+    fn log_update(&self, entry: &UpdateLogEntry) -> Result<(), FgdbError> {
+        let mut conn = Conn::with_paths(&self.paths)?;
+        let mut tx = conn.create_tx()?;
+        UpdateLog::record(&mut tx, entry)?;
         Ok(())
     }
+
+    // Returns the number of rows in each table.  Counts are None if the database cannot be opened.
+    fn row_counts(&self) -> Vec<(String, Option<usize>)> {
+        match Conn::with_paths(&self.paths).and_then(|mut conn| Ok(UpdateLog::row_counts(&mut conn.create_rollback_tx()?))) {
+            Ok(counts) => counts,
+            Err(_) => TABLES.iter().map(|table| (table.name().to_string(), None)).collect(),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::concurrency::env_pool::EnvPoolGuard;
+    use crate::repo::remote_repository;
+    use std::fs;
+    use std::io::{Cursor, Write};
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    #[test]
+    fn test_cancelled_update_does_nothing() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let updater = Updater::with_paths(PathBuf::from("missing"), Paths::with_root(Path::new("missing"), GameVariant::Poe1, Env::Prod))
+            .allow_unsigned(true);
+        assert!(matches!(updater.update_cancellable(Policy::Force, &token), Err(FgdbError::Cancelled)));
+    }
+
+    #[test]
+    fn test_failed_update_is_logged() {
+        let env_guard = EnvPoolGuard::new();
+        let updater = Updater::with_paths(PathBuf::from("missing"), Paths::new(env_guard.env)).allow_unsigned(true);
+        assert!(updater.update(Policy::Force).is_err());
+        updater.update(Policy::Skip).unwrap();
+
+        let history = updater.history(10).unwrap();
+        assert_eq!(2, history.len());
+        assert_eq!((Policy::Skip, UpdateOutcome::Skipped), (history[0].policy, history[0].outcome));
+        let failed = &history[1];
+        assert_eq!((Policy::Force, UpdateOutcome::Failed), (failed.policy, failed.outcome));
+        assert!(failed.error.is_some());
+        assert_eq!(Path::new("missing").join(updater.game_variant.to_string()).display().to_string(), failed.location);
+        assert_eq!(TABLES.len(), failed.row_counts.len());
+        assert!(failed.row_counts.iter().all(|counts| counts.before.is_some() && counts.before == counts.after));
+    }
+
+    #[test]
+    fn test_offline_fallback_keeps_stale_data() {
+        let env_guard = EnvPoolGuard::new();
+        let updater = Updater::with_paths(PathBuf::from("missing"), Paths::new(env_guard.env)).allow_unsigned(true).retry_policy(RetryPolicy::none());
+        assert!(updater.update(Policy::Auto).is_err());
+
+        let updater = updater.offline_fallback(true);
+        let reports = Mutex::new(Vec::new());
+        let observer = |progress: &Progress| reports.lock().unwrap().push(progress.clone());
+        assert!(!updater.update_with_progress(Policy::Auto, &observer, &CancellationToken::new()).unwrap());
+        assert!(matches!(reports.lock().unwrap().last(), Some(Progress::Stale { .. })));
+        let entry = &updater.history(1).unwrap()[0];
+        assert_eq!(UpdateOutcome::Stale, entry.outcome);
+        assert!(entry.error.is_some());
+
+        // Offline fallback applies only to Policy::Auto.
+        assert!(updater.update(Policy::Force).is_err());
+    }
+
+    #[test]
+    fn test_unsigned_bundle_is_refused() {
+        let env_guard = EnvPoolGuard::new();
+        let paths = Paths::new(env_guard.env);
+        paths.create_dirs([Dir::CacheZip, Dir::CacheTimestamp, Dir::CacheJson].iter()).unwrap();
+        let root_path = env_guard.out_dir("test_unsigned_bundle_is_refused");
+        let repository_path = root_path.join(paths.game_variant().to_string());
+        fs::create_dir_all(repository_path.join(consts::REPOSITORY_ZIP_DIR)).unwrap();
+        fs::create_dir_all(repository_path.join(consts::REPOSITORY_TIMESTAMP_DIR)).unwrap();
+        fs::write(repository_path.join(consts::REPOSITORY_TIMESTAMP_DIR).join(consts::TIMESTAMP_FILE_NAME), "{}").unwrap();
+        let zip_path = repository_path.join(consts::REPOSITORY_ZIP_DIR).join(consts::REPOSITORY_ZIP_FILE_NAME);
+        let signature_path = repository_path.join(consts::REPOSITORY_ZIP_DIR).join(consts::REPOSITORY_SIGNATURE_FILE_NAME);
+        fs::write(&zip_path, b"bundle").unwrap();
+
+        let (trusted, untrusted) = (signature::tests::generate_key_pair(), signature::tests::generate_key_pair());
+        let config = FgdbConfig {
+            public_keys: vec![signature::public_key(&trusted).unwrap()],
+            ..FgdbConfig::local(root_path, paths.game_variant())
+        };
+        let updater = Updater::with_config(config, paths);
+        assert!(matches!(updater.update(Policy::Force), Err(FgdbError::Signature(_))));
+
+        signature::sign_bundle(&zip_path, &signature_path, &[&untrusted]).unwrap();
+        assert!(matches!(updater.update(Policy::Force), Err(FgdbError::Signature(_))));
+        assert_eq!(UpdateOutcome::Failed, updater.history(1).unwrap()[0].outcome);
+
+        signature::sign_bundle(&zip_path, &signature_path, &[&trusted]).unwrap();
+        assert!(!matches!(updater.update(Policy::Force), Err(FgdbError::Signature(_))));
+    }
+
+    #[test]
+    fn test_rejected_bundle_is_retried() {
+        let env_guard = EnvPoolGuard::new();
+        let root_path = env_guard.out_dir("test_rejected_bundle_is_retried");
+        // The cache is kept under root_path so that no other test's cache timestamp is seen.
+        let paths = Paths::with_root(&root_path.join("data"), get_config().game_variant, env_guard.env);
+        paths.create_dirs([Dir::CacheZip, Dir::CacheTimestamp, Dir::CacheJson].iter()).unwrap();
+        let repository_path = root_path.join(paths.game_variant().to_string());
+        fs::create_dir_all(repository_path.join(consts::REPOSITORY_ZIP_DIR)).unwrap();
+        fs::create_dir_all(repository_path.join(consts::REPOSITORY_TIMESTAMP_DIR)).unwrap();
+        fs::write(repository_path.join(consts::REPOSITORY_TIMESTAMP_DIR).join(consts::TIMESTAMP_FILE_NAME), "{}").unwrap();
+        fs::write(repository_path.join(consts::REPOSITORY_ZIP_DIR).join(consts::REPOSITORY_ZIP_FILE_NAME), b"not a zip file").unwrap();
+
+        // The rejected bundle does not make the cache current, so the next update tries again rather than skipping.
+        let updater = Updater::with_paths(root_path, paths.clone()).allow_unsigned(true).retry_policy(RetryPolicy::none());
+        assert!(matches!(updater.update(Policy::Auto), Err(FgdbError::Repository(..))));
+        assert!(matches!(updater.update(Policy::Auto), Err(FgdbError::Repository(..))));
+        assert!(!paths.lookup(Dir::CacheTimestamp).join(consts::TIMESTAMP_FILE_NAME).exists());
+
+        // Without public keys the bundle is refused unless unsigned bundles are allowed.
+        let updater = updater.allow_unsigned(false);
+        let result = updater.update(Policy::Force);
+        assert!(matches!(result, Err(FgdbError::Config(message, _)) if message.contains("public_keys is not set")));
+    }
+
+    #[test]
+    fn test_remote_update_logs_bundle_version() {
+        let env_guard = EnvPoolGuard::new();
+        let root_path = env_guard.out_dir("test_remote_update_logs_bundle_version");
+        let paths = Paths::with_root(&root_path, get_config().game_variant, env_guard.env);
+        paths.create_dirs([Dir::CacheZip, Dir::CacheTimestamp, Dir::CacheJson].iter()).unwrap();
+        // The bundle holds the test data.
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for table in TABLES.iter() {
+            let file_name = format!("{}.json", table.name());
+            writer.start_file(file_name.as_str(), SimpleFileOptions::default()).unwrap();
+            writer.write_all(&fs::read(Paths::new(env_guard.env).lookup(Dir::AssetsJsonTest).join(&file_name)).unwrap()).unwrap();
+        }
+        let bundle = writer.finish().unwrap().into_inner();
+        let url = remote_repository::tests::serve(bundle, Arc::new(Mutex::new("\"v1\"".to_string())), Arc::new(Mutex::new(Vec::new())));
+        let config = FgdbConfig {
+            repository: RepositoryType::Remote,
+            repository_url: Some(url),
+            ..FgdbConfig::local(root_path.clone(), paths.game_variant())
+        };
+        let updater = Updater::with_config(config, paths).allow_unsigned(true);
+
+        assert!(updater.update(Policy::Auto).unwrap());
+        let entry = &updater.history(1).unwrap()[0];
+        assert_eq!((None, Some("\"v1\"".to_string())), (entry.old_timestamp.clone(), entry.new_timestamp.clone()));
+
+        assert!(!updater.update(Policy::Auto).unwrap());
+        let entry = &updater.history(1).unwrap()[0];
+        assert_eq!(UpdateOutcome::Skipped, entry.outcome);
+        assert_eq!(Some("\"v1\"".to_string()), entry.old_timestamp);
+    }
 
     #[test]
     #[ignore]
@@ -5,22 +5,47 @@
 // Database Name
 pub const DB_NAME: &str = "fgdb.db";
 
+// Database connection defaults.
+pub const DB_BUSY_TIMEOUT_MS: u64 = 5000;
+pub const DB_MAX_IDLE_READERS: usize = 4;
+
+// Repository retry defaults.
+pub const REPO_RETRY_MAX_ATTEMPTS: u32 = 3;
+pub const REPO_RETRY_INITIAL_BACKOFF_MS: u64 = 500;
+pub const REPO_RETRY_MAX_BACKOFF_MS: u64 = 30 * 1000;
+
+// Bundle extraction limits.
+pub const BUNDLE_MAX_ENTRIES: usize = 64;
+pub const BUNDLE_MAX_ENTRY_SIZE: u64 = 256 * 1024 * 1024;
+pub const BUNDLE_MAX_TOTAL_SIZE: u64 = 1024 * 1024 * 1024;
+pub const BUNDLE_MAX_COMPRESSION_RATIO: u64 = 100;
+
+// Price refresh defaults.
+pub const PRICE_REFRESH_INTERVAL_SECS: u64 = 60 * 60;
+pub const PRICE_REFRESH_JITTER_SECS: u64 = 5 * 60;
+pub const PRICE_REFRESH_MIN_INTERVAL_SECS: u64 = 15 * 60;
+
 // Environment Paths
 pub const PROD_RELATIVE_PATH: &str = "prod";
-pub const TEST1_RELATIVE_PATH: &str = "test1";
-pub const TEST2_RELATIVE_PATH: &str = "test2";
-pub const TEST3_RELATIVE_PATH: &str = "test3";
-pub const TEST4_RELATIVE_PATH: &str = "test4";
-pub const TEST5_RELATIVE_PATH: &str = "test5";
+pub const TEST_RELATIVE_PATH_PREFIX: &str = "test";
 
 // File Names
 pub const CONFIG_TOML: &str = "fgdb.toml";
+pub const IMAGE_FILE_EXTENSION: &str = "png";
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+pub const MARKDOWN_CREDITS_FILE_NAME: &str = "CREDITS.md";
+pub const REPOSITORY_SIGNATURE_FILE_NAME: &str = "poedb.zip.sig";
 pub const REPOSITORY_ZIP_FILE_NAME: &str = "poedb.zip";
+pub const TEXT_CREDITS_FILE_NAME: &str = "CREDITS.txt";
 pub const TIMESTAMP_FILE_NAME: &str = "timestamp.json";
+pub const VALIDATORS_FILE_NAME: &str = "validators.json";
 
 // Relative Directories
+pub const ASSETS_IMAGES_TEST_DIR: &str = "images/test";
 pub const ASSETS_JSON_TEST_DIR: &str = "json/test";
+pub const ASSETS_SOUNDS_TEST_DIR: &str = "sounds/test";
 pub const CACHE_DIR: &str = "cache";
+pub const CACHE_IMAGES_DIR: &str = "images";
 pub const CACHE_JSON_DIR: &str = "json";
 pub const CACHE_TIMESTAMP_DIR: &str = "timestamp";
 pub const CACHE_ZIP_DIR: &str = "zip";
@@ -28,14 +53,56 @@ pub const ENV_DB_DIR: &str = "db";
 pub const ENV_OUT_DIR: &str = "out";
 pub const REPOSITORY_TIMESTAMP_DIR: &str = "timestamp";
 pub const REPOSITORY_ZIP_DIR: &str = "zip";
+pub const SOUND_PACK_DIR: &str = "fgsounds";
+
+// Environment variables
+pub const CONFIG_ENV_VAR: &str = "FGDB_CONFIG";
+pub const CONNECT_TIMEOUT_SECS_ENV_VAR: &str = "FGDB_CONNECT_TIMEOUT_SECS";
+pub const DATA_ROOT_ENV_VAR: &str = "FGDB_DATA_ROOT";
+pub const GAME_VARIANT_ENV_VAR: &str = "FGDB_GAME_VARIANT";
+pub const PUBLIC_KEYS_ENV_VAR: &str = "FGDB_PUBLIC_KEYS";
+pub const REPOSITORY_ENV_VAR: &str = "FGDB_REPOSITORY";
+pub const REPOSITORY_URL_ENV_VAR: &str = "FGDB_REPOSITORY_URL";
+pub const ROOT_PATH_ENV_VAR: &str = "FGDB_ROOT_PATH";
+pub const SOUND_PATH_ENV_VAR: &str = "FGDB_SOUND_PATH";
+pub const TIMEOUT_SECS_ENV_VAR: &str = "FGDB_TIMEOUT_SECS";
+
+// Name of the fgdb directory within platform data and config directories.
+pub const APP_DIR_NAME: &str = "fgdb";
+
+// Prefix of the per-process directory within the system temporary directory which holds test environments.
+pub const TEST_ENV_ROOT_PREFIX: &str = "fgdb-test-";
 
 // Miscellaneous Directories
 pub const ASSETS_DIR: &str = "assets";
+pub const DEFAULT_REPOSITORY_DIR: &str = "repository";
 pub const ENV_DIR: &str = "env";
 
 // Game variants
 pub const GAME_VARIANT_POE1: &str = "poe1";
 pub const GAME_VARIANT_POE2: &str = "poe2";
 
+// HTTP timeouts in seconds used when connect_timeout_secs and timeout_secs are not set.  The timeout bounds a whole
+// request, including the download of the bundle.
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+pub const DEFAULT_TIMEOUT_SECS: u64 = 600;
+
+// Image cache limits.
+pub const MAX_IMAGE_DIMENSION: u32 = 1024;
+pub const MAX_IMAGE_FILE_SIZE: u64 = 1024 * 1024;
+
+// Extension given to files while they are being written.
+pub const TEMP_FILE_EXTENSION: &str = "tmp";
+
+// Extension given to the freshness marker of a downloaded bundle until the bundle has been extracted and imported.
+pub const PENDING_FILE_EXTENSION: &str = "pending";
+
+// Extension of the file identifying the source of a partial download, used to decide whether the download is resumed.
+pub const PARTIAL_SOURCE_FILE_EXTENSION: &str = "tmp.source";
+
+// Extensions given to the directory a bundle is extracted into and to the directory it replaces while they are swapped.
+pub const STAGING_DIR_EXTENSION: &str = "staging";
+pub const RETIRED_DIR_EXTENSION: &str = "old";
+
 // Width in spaces for a JSON tab.
 pub const JSON_TAB: usize = 2;
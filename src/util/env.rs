@@ -5,59 +5,34 @@
 use crate::util::consts;
 use std::fmt;
 use std::fmt::Display;
-use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
 
-//static PROD_ENV: usize = Env::Prod as usize;
-
-static FIRST_TEST_ENV: usize = Env::Test1 as usize;
-static LAST_TEST_ENV: usize = Env::Test5 as usize;
-
-#[derive(Copy, Clone, Debug, EnumIter, PartialEq)]
+/// An environment, i.e., a database and output directory.  The production environment lives under the data root.
+/// Test environments are created on demand by EnvPool in the test environment root and are identified by number.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Env {
     Prod,
-    Test1,
-    Test2,
-    Test3,
-    Test4,
-    Test5,
+    Test(u32),
 }
 
 impl Display for Env {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Env::Prod => "Master",
-                Env::Test1 => "Test1",
-                Env::Test2 => "Test2",
-                Env::Test3 => "Test3",
-                Env::Test4 => "Test4",
-                Env::Test5 => "Test5",
-            }
-        )
+        match self {
+            Env::Prod => write!(f, "Master"),
+            Env::Test(id) => write!(f, "Test{}", id),
+        }
     }
 }
 
 impl Env {
-    pub fn relative_path(&self) -> &'static str {
+    pub fn relative_path(&self) -> String {
         match self {
-            Env::Prod => consts::PROD_RELATIVE_PATH,
-            Env::Test1 => consts::TEST1_RELATIVE_PATH,
-            Env::Test2 => consts::TEST2_RELATIVE_PATH,
-            Env::Test3 => consts::TEST3_RELATIVE_PATH,
-            Env::Test4 => consts::TEST4_RELATIVE_PATH,
-            Env::Test5 => consts::TEST5_RELATIVE_PATH,
+            Env::Prod => consts::PROD_RELATIVE_PATH.to_string(),
+            Env::Test(id) => format!("{}{}", consts::TEST_RELATIVE_PATH_PREFIX, id),
         }
     }
 
     pub fn is_test_env(env: Env) -> bool {
-        env as usize >= FIRST_TEST_ENV && env as usize <= LAST_TEST_ENV
-    }
-
-    pub fn test_envs() -> impl Iterator<Item=Env> {
-        Env::iter().skip(FIRST_TEST_ENV).take(LAST_TEST_ENV - FIRST_TEST_ENV + 1)
+        matches!(env, Env::Test(_))
     }
 }
 
@@ -67,22 +42,13 @@ mod tests {
 
     #[test]
     fn test_is_test_env_works() {
-        for env in Env::test_envs() {
-            assert!(Env::is_test_env(env));
-        }
+        assert!(!Env::is_test_env(Env::Prod));
+        assert!(Env::is_test_env(Env::Test(1)));
     }
 
     #[test]
-    fn test_test_envs_works() {
-        let mut count = 0;
-        let mut expected_env = Env::Test1 as usize;
-        for env in Env::test_envs() {
-            assert!(env as usize >= FIRST_TEST_ENV);
-            assert!(env as usize <= LAST_TEST_ENV);
-            assert_eq!(expected_env, env as usize);
-            count += 1;
-            expected_env += 1
-        }
-        assert_eq!(count, LAST_TEST_ENV - FIRST_TEST_ENV + 1);
+    fn test_relative_path_works() {
+        assert_eq!("prod", Env::Prod.relative_path());
+        assert_eq!("test12", Env::Test(12).relative_path());
     }
 }
@@ -2,10 +2,48 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 1/16/2026.
 
+use rusqlite::types::Type;
 use rusqlite::Error as RusqliteError;
+use serde::{Deserialize, Serialize};
+use std::error::Error as StdError;
+use std::io;
 use thiserror::Error;
 
-const UNKNOWN_COLUMN: usize = usize::MAX;
+// Column index used for conversion failures raised before the failing column is known.  RowReader replaces it with
+// the index of the column responsible for the failure.
+pub(crate) const UNKNOWN_COLUMN: usize = usize::MAX;
+
+/// The underlying cause of an FgdbError, e.g., the I/O, HTTP or parse error from which it was raised.
+pub type BoxedError = Box<dyn StdError + Send + Sync + 'static>;
+
+/// A value stored in the database could not be converted to its Rust type.
+#[derive(Debug, Error)]
+#[error("Invalid value in column '{column}' (index {index}) of table '{table}' for row '{primary_key}'; SQL type {sql_type}: {source}")]
+pub struct FgdbColumnError {
+    // The name of the table.
+    pub table: String,
+
+    // The name of the column holding the invalid value.
+    pub column: String,
+
+    // The index of the column within the query.
+    pub index: usize,
+
+    // The primary key of the row holding the invalid value.
+    pub primary_key: String,
+
+    // The SQLite type of the stored value.
+    pub sql_type: Type,
+
+    #[source]
+    pub source: BoxedError,
+}
+
+impl From<FgdbColumnError> for RusqliteError {
+    fn from(e: FgdbColumnError) -> RusqliteError {
+        RusqliteError::FromSqlConversionFailure(e.index, e.sql_type, Box::new(e))
+    }
+}
 
 #[derive(Debug, Error)]
 pub enum FgdbDatFileError {
@@ -13,21 +51,170 @@ pub enum FgdbDatFileError {
     LookupFailed(String),
 }
 
+/// The error type returned by the public fgdb API.  Each variant has a stable error code, obtained using code, which
+/// applications may use to select a user message.  Codes are never reused or changed once published.
+#[derive(Debug, Error)]
+pub enum FgdbError {
+    #[error("Operation cancelled.")]
+    Cancelled,
+
+    #[error(transparent)]
+    Column(FgdbColumnError),
+
+    #[error("Configuration error: {0}")]
+    Config(String, #[source] Option<BoxedError>),
+
+    #[error(transparent)]
+    DatFile(#[from] FgdbDatFileError),
+
+    #[error(transparent)]
+    FromSql(#[from] FgdbFromSqlError),
+
+    #[error(transparent)]
+    Image(#[from] FgdbImageError),
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Json(#[from] FgdbJsonError),
+
+    #[error("Network error: {0}")]
+    Network(String, #[source] Option<BoxedError>),
+
+    #[error(transparent)]
+    Parse(#[from] FgdbParseError),
+
+    #[error(transparent)]
+    Range(#[from] FgdbRangeError),
+
+    #[error("Repository error: {0}")]
+    Repository(String, #[source] Option<BoxedError>),
+
+    #[error("Signature error: {0}")]
+    Signature(String),
+
+    #[error(transparent)]
+    SoundPack(#[from] FgdbSoundPackError),
+
+    #[error(transparent)]
+    Sql(RusqliteError),
+
+    #[error("Repository unreachable: {0}")]
+    Unreachable(String, #[source] io::Error),
+}
+
+impl FgdbError {
+    /// Returns the stable code identifying the kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            FgdbError::Cancelled => "FGDB-CAN-001",
+            FgdbError::Column(_) => "FGDB-SQL-002",
+            FgdbError::Config(..) => "FGDB-CFG-001",
+            FgdbError::DatFile(_) => "FGDB-VAL-004",
+            FgdbError::FromSql(_) => "FGDB-VAL-003",
+            FgdbError::Image(_) => "FGDB-VAL-005",
+            FgdbError::Io(_) => "FGDB-IO-001",
+            FgdbError::Json(FgdbJsonError::Invalid(..)) => "FGDB-JSON-001",
+            FgdbError::Json(FgdbJsonError::Range(..)) => "FGDB-JSON-002",
+            FgdbError::Network(..) => "FGDB-NET-001",
+            FgdbError::Parse(_) => "FGDB-VAL-001",
+            FgdbError::Range(_) => "FGDB-VAL-002",
+            FgdbError::Repository(..) => "FGDB-REPO-001",
+            FgdbError::Signature(_) => "FGDB-SIG-001",
+            FgdbError::SoundPack(_) => "FGDB-VAL-006",
+            FgdbError::Sql(_) => "FGDB-SQL-001",
+            FgdbError::Unreachable(..) => "FGDB-REPO-002",
+        }
+    }
+
+    /// Returns true if the operation which failed with this error may succeed if retried, e.g., after a network
+    /// timeout.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            FgdbError::Network(..) => true,
+            FgdbError::Io(e) | FgdbError::Unreachable(_, e) => matches!(
+                e.kind(),
+                io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::ConnectionRefused
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::Interrupted
+                    | io::ErrorKind::NotConnected
+                    | io::ErrorKind::TimedOut
+                    | io::ErrorKind::UnexpectedEof
+                    | io::ErrorKind::WouldBlock
+            ),
+            _ => false,
+        }
+    }
+
+    /// Returns true if the error shows that a repository could not be reached, e.g., because the network is down or
+    /// the drive holding a local repository is not mounted.  Local I/O errors, e.g., a full disk while writing the cache,
+    /// are not.
+    pub fn is_unreachable(&self) -> bool {
+        matches!(self, FgdbError::Network(..) | FgdbError::Unreachable(..))
+    }
+}
+
+impl From<FgdbColumnError> for FgdbError {
+    fn from(e: FgdbColumnError) -> FgdbError {
+        FgdbError::Column(e)
+    }
+}
+
+// Conversion failures attributed to a column by RowReader are surfaced as FgdbError::Column.
+impl From<RusqliteError> for FgdbError {
+    fn from(e: RusqliteError) -> FgdbError {
+        match e {
+            RusqliteError::FromSqlConversionFailure(index, sql_type, source) => match source.downcast::<FgdbColumnError>() {
+                Ok(column_error) => FgdbError::Column(*column_error),
+                Err(source) => FgdbError::Sql(RusqliteError::FromSqlConversionFailure(index, sql_type, source)),
+            },
+            e => FgdbError::Sql(e),
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum FgdbFromSqlError {
     #[error("Error constructing Icon: {0}.")]
     Icon(String),
 
+    #[error("Error constructing ItemArt: {0}.")]
+    ItemArt(String),
+
     #[error("Error constructing Sound: {0}.")]
     Sound(String),
 }
 
 impl From<FgdbFromSqlError> for RusqliteError {
     fn from(e: FgdbFromSqlError) -> RusqliteError {
-        RusqliteError::FromSqlConversionFailure(UNKNOWN_COLUMN, rusqlite::types::Type::Text, Box::new(e))
+        RusqliteError::FromSqlConversionFailure(UNKNOWN_COLUMN, Type::Text, Box::new(e))
     }
 }
 
+#[derive(Debug, Error)]
+pub enum FgdbImageError {
+    #[error("Image for '{0}' is not a valid PNG: {1}.")]
+    InvalidPng(String, String),
+
+    #[error("Image for '{0}' exceeds the maximum file size of {1} bytes.")]
+    TooLarge(String, u64),
+
+    #[error("Image for '{0}' is {1}x{2} pixels; the maximum width and height is {3}.")]
+    TooManyPixels(String, u32, u32, u32),
+}
+
+#[derive(Debug, Error)]
+pub enum FgdbJsonError {
+    #[error("Invalid JSON at '{0}': {1}")]
+    Invalid(String, #[source] serde_json::Error),
+
+    #[error("Value out of range at '{0}': {1}")]
+    Range(String, #[source] FgdbRangeError),
+}
+
 #[derive(Debug, Error)]
 pub enum FgdbParseError {
     #[error("Invalid armor type '{0}'.")]
@@ -36,29 +223,53 @@ pub enum FgdbParseError {
     #[error("Invalid color basis '{0}'.")]
     InvalidColorBasis(String),
 
+    #[error("Invalid color vision '{0}'.")]
+    InvalidColorVision(String),
+
+    #[error("Invalid format version '{0}'.")]
+    InvalidFormatVersion(u32),
+
+    #[error("Invalid game variant '{0}'.")]
+    InvalidGameVariant(String),
+
     #[error("Invalid icon shape '{0}'.")]
     InvalidIconShape(String),
 
     #[error("Invalid liquidity '{0}'.")]
     InvalidLiquidity(String),
 
+    #[error("Invalid policy '{0}'.")]
+    InvalidPolicy(String),
+
+    #[error("Invalid public key '{0}'.")]
+    InvalidPublicKey(String),
+
     #[error("Invalid rarity '{0}'.")]
     InvalidRarity(String),
 
+    #[error("Invalid repository '{0}'.")]
+    InvalidRepository(String),
+
     #[error("Invalid stock color '{0}'.")]
     InvalidStockColor(String),
 
     #[error("Invalid stock sound '{0}'.")]
     InvalidStockSound(String),
+
+    #[error("Invalid update outcome '{0}'.")]
+    InvalidUpdateOutcome(String),
+
+    #[error("Invalid URL '{0}'.")]
+    InvalidUrl(String),
 }
 
 impl From<FgdbParseError> for RusqliteError {
     fn from(e: FgdbParseError) -> RusqliteError {
-        RusqliteError::FromSqlConversionFailure(UNKNOWN_COLUMN, rusqlite::types::Type::Text, Box::new(e))
+        RusqliteError::FromSqlConversionFailure(UNKNOWN_COLUMN, Type::Text, Box::new(e))
     }
 }
 
-#[derive(Debug, Error)]
+#[derive(Debug, Deserialize, Error, Serialize)]
 pub enum FgdbRangeError {
     #[error("Font size out of range [{0}-{1}]: {2}.")]
     FontSize(u8, u8, u8),
@@ -84,6 +295,61 @@ pub enum FgdbRangeError {
 
 impl From<FgdbRangeError> for RusqliteError {
     fn from(e: FgdbRangeError) -> RusqliteError {
-        RusqliteError::FromSqlConversionFailure(UNKNOWN_COLUMN, rusqlite::types::Type::Text, Box::new(e))
+        RusqliteError::FromSqlConversionFailure(UNKNOWN_COLUMN, Type::Text, Box::new(e))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FgdbSoundPackError {
+    #[error("File name '{1}' for sound '{0}' is not a plain file name.")]
+    InvalidSoundFileName(String, String),
+
+    #[error("License '{1}' for sound '{0}' is missing from the licenses table.")]
+    MissingLicense(String, String),
+
+    #[error("Sound '{0}' is missing from the sounds table.")]
+    MissingSound(String),
+
+    #[error("Sound directory is not configured.  Set sound_path in fgdb.toml.")]
+    MissingSoundDir(),
+
+    #[error("File '{1}' for sound '{0}' does not exist.")]
+    MissingSoundFile(String, String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_works() {
+        assert_eq!("FGDB-CAN-001", FgdbError::Cancelled.code());
+        assert_eq!("FGDB-CFG-001", FgdbError::Config("missing".to_string(), None).code());
+        assert_eq!("FGDB-IO-001", FgdbError::from(io::Error::other("io")).code());
+        assert_eq!("FGDB-SQL-001", FgdbError::from(RusqliteError::QueryReturnedNoRows).code());
+        assert_eq!("FGDB-VAL-002", FgdbError::from(FgdbRangeError::StackSize()).code());
+    }
+
+    #[test]
+    fn test_is_unreachable_works() {
+        assert!(FgdbError::Network("timed out".to_string(), None).is_unreachable());
+        assert!(FgdbError::Unreachable("'repo' not mounted".to_string(), io::Error::from(io::ErrorKind::NotFound)).is_unreachable());
+        assert!(!FgdbError::from(io::Error::from(io::ErrorKind::StorageFull)).is_unreachable());
+        assert!(!FgdbError::from(io::Error::from(io::ErrorKind::PermissionDenied)).is_unreachable());
+    }
+
+    #[test]
+    fn test_from_rusqlite_error_surfaces_column_error() {
+        let column_error = FgdbColumnError {
+            table: "classes".to_string(),
+            column: "highest_rarity".to_string(),
+            index: 1,
+            primary_key: "Amulets".to_string(),
+            sql_type: Type::Text,
+            source: Box::new(FgdbParseError::InvalidRarity("Epic".to_string())),
+        };
+        let e = FgdbError::from(RusqliteError::from(column_error));
+        assert!(matches!(e, FgdbError::Column(FgdbColumnError { index: 1, .. })));
+        assert_eq!("FGDB-SQL-002", e.code());
     }
 }
@@ -0,0 +1,39 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::config::fgdb_config::{try_get_config, FgdbConfig};
+use crate::util::consts;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, PoisonError};
+use std::time::Duration;
+use ureq::Agent;
+
+// The shared agents keyed by connect timeout and timeout.  Clones of an agent share its connection pool.
+static AGENTS: LazyLock<Mutex<HashMap<(Duration, Duration), Agent>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Returns the agent for HTTP requests made with the timeouts of config.  Repositories and image fetchers created with
+/// the same timeouts share one agent and so one connection pool.
+pub fn agent(config: &FgdbConfig) -> Agent {
+    shared_agent(config.connect_timeout(), config.timeout())
+}
+
+/// Returns the agent for the timeouts of the global configuration, or for the default timeouts if the configuration
+/// cannot be loaded.
+pub fn default_agent() -> Agent {
+    match try_get_config() {
+        Ok(config) => agent(config),
+        Err(_) => shared_agent(Duration::from_secs(consts::DEFAULT_CONNECT_TIMEOUT_SECS), Duration::from_secs(consts::DEFAULT_TIMEOUT_SECS)),
+    }
+}
+
+/// Creates an agent whose connections must be established within connect_timeout and whose requests, including
+/// reading the response body, must complete within timeout.
+pub fn create_agent(connect_timeout: Duration, timeout: Duration) -> Agent {
+    Agent::config_builder().timeout_connect(Some(connect_timeout)).timeout_global(Some(timeout)).build().into()
+}
+
+fn shared_agent(connect_timeout: Duration, timeout: Duration) -> Agent {
+    let mut agents = AGENTS.lock().unwrap_or_else(PoisonError::into_inner);
+    agents.entry((connect_timeout, timeout)).or_insert_with(|| create_agent(connect_timeout, timeout)).clone()
+}
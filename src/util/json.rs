@@ -0,0 +1,252 @@
+// Copyright (c) 2026 By David "Hankinsohl" Hankins.
+// This software is licensed under the terms of the MIT License.
+// Created by Hankinsohl on 10/18/2026.
+
+use crate::types::format_version::FormatVersion;
+use crate::util::consts;
+use crate::util::errors::{FgdbError, FgdbJsonError, FgdbRangeError};
+use serde::de::{DeserializeOwned, Error as DeError, IntoDeserializer, MapAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json_fmt::JsonFormat;
+use std::cell::Cell;
+use std::fmt;
+use std::io;
+use std::io::Read;
+use std::marker::PhantomData;
+
+// Serde reduces custom errors to their message, so the range error raised by a validating Deserialize impl is encoded
+// as JSON between two tags at the start of the message, from which it is recovered along with the path of the offending
+// value once deserialization fails.
+const RANGE_ERROR_TAG: &str = "#range#";
+
+thread_local! {
+    // The format used to serialize scalar newtypes.  Set for the duration of to_string.
+    static FORMAT_VERSION: Cell<FormatVersion> = const { Cell::new(FormatVersion::Nested) };
+}
+
+// Visits either representation of a scalar newtype: the bare value or an object holding the value in a single field.
+// Values are passed on to T as they are visited, so errors raised by T keep their path and message.
+struct NewtypeVisitor<T> {
+    field: &'static str,
+    value: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for NewtypeVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a value or an object with the single field `{}`", self.field)
+    }
+
+    fn visit_bool<E: DeError>(self, v: bool) -> Result<T, E> {
+        T::deserialize(v.into_deserializer())
+    }
+
+    fn visit_i64<E: DeError>(self, v: i64) -> Result<T, E> {
+        T::deserialize(v.into_deserializer())
+    }
+
+    fn visit_u64<E: DeError>(self, v: u64) -> Result<T, E> {
+        T::deserialize(v.into_deserializer())
+    }
+
+    fn visit_f64<E: DeError>(self, v: f64) -> Result<T, E> {
+        T::deserialize(v.into_deserializer())
+    }
+
+    fn visit_str<E: DeError>(self, v: &str) -> Result<T, E> {
+        T::deserialize(v.into_deserializer())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+        let mut value = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key != self.field {
+                return Err(A::Error::custom(format!("unknown field `{key}`, expected `{}`", self.field)));
+            }
+            if value.is_some() {
+                return Err(A::Error::duplicate_field(self.field));
+            }
+            value = Some(map.next_value()?);
+        }
+        value.ok_or_else(|| A::Error::missing_field(self.field))
+    }
+}
+
+/// Deserializes the value of a scalar newtype from either the flat or the nested representation.  field is the name of
+/// the single field used by the nested representation.
+pub(crate) fn deserialize_newtype<'de, D: Deserializer<'de>, T: Deserialize<'de>>(deserializer: D, field: &'static str) -> Result<T, D::Error> {
+    deserializer.deserialize_any(NewtypeVisitor { field, value: PhantomData })
+}
+
+// Restores the format in use before to_string when dropped, so a panic while serializing cannot leave it changed.
+struct FormatVersionGuard(FormatVersion);
+
+impl Drop for FormatVersionGuard {
+    fn drop(&mut self) {
+        FORMAT_VERSION.set(self.0);
+    }
+}
+
+/// Serializes the value of a scalar newtype using the format selected by to_string.
+pub(crate) fn serialize_newtype<S: Serializer, T: Serialize>(serializer: S, name: &'static str, field: &'static str, value: &T) -> Result<S::Ok, S::Error> {
+    match FORMAT_VERSION.get() {
+        FormatVersion::Nested => {
+            let mut state = serializer.serialize_struct(name, 1)?;
+            state.serialize_field(field, value)?;
+            state.end()
+        }
+        FormatVersion::Flat => value.serialize(serializer),
+    }
+}
+
+/// Serializes value as pretty-printed, ASCII JSON in the given format, reporting the JSON path of the value responsible
+/// for any failure.
+pub fn to_string<T: Serialize>(value: &T, format_version: FormatVersion) -> Result<String, FgdbError> {
+    let _guard = FormatVersionGuard(FORMAT_VERSION.replace(format_version));
+    let format = JsonFormat::pretty().indent_width(Some(consts::JSON_TAB)).ascii(true);
+    format.format_to_string(value).map_err(|e| {
+        // The formatter does not track the path, so serialization is repeated with path tracking to locate the failure.
+        let path = match serde_path_to_error::serialize(value, &mut serde_json::Serializer::new(io::sink())) {
+            Err(path_error) => path_error.path().to_string(),
+            Ok(_) => ".".to_string(),
+        };
+        FgdbJsonError::Invalid(path, e).into()
+    })
+}
+
+/// Converts a range error raised while deserializing a constrained type into a serde error.  Use with map_err from
+/// Deserialize impls so that from_reader and from_str can report the range error.
+pub(crate) fn range_error<E: DeError>(e: FgdbRangeError) -> E {
+    match serde_json::to_string(&e) {
+        Ok(encoded) => E::custom(format!("{RANGE_ERROR_TAG}{encoded}{RANGE_ERROR_TAG} {e}")),
+        Err(_) => E::custom(e),
+    }
+}
+
+// Recovers the range error encoded in message by range_error, if any.
+fn decode_range_error(message: &str) -> Option<FgdbRangeError> {
+    let (encoded, _) = message.strip_prefix(RANGE_ERROR_TAG)?.split_once(RANGE_ERROR_TAG)?;
+    serde_json::from_str(encoded).ok()
+}
+
+/// Deserializes JSON from reader, reporting the JSON path of the value responsible for any failure.
+pub fn from_reader<T: DeserializeOwned>(reader: impl Read) -> Result<T, FgdbJsonError> {
+    deserialize(&mut serde_json::Deserializer::from_reader(reader))
+}
+
+/// Deserializes JSON from s, reporting the JSON path of the value responsible for any failure.
+pub fn from_str<T: DeserializeOwned>(s: &str) -> Result<T, FgdbJsonError> {
+    deserialize(&mut serde_json::Deserializer::from_str(s))
+}
+
+fn deserialize<'de, T: DeserializeOwned, R: serde_json::de::Read<'de>>(deserializer: &mut serde_json::Deserializer<R>) -> Result<T, FgdbJsonError> {
+    let value = serde_path_to_error::deserialize(&mut *deserializer).map_err(|e| {
+        let path = e.path().to_string();
+        match decode_range_error(&e.inner().to_string()) {
+            Some(range_error) => FgdbJsonError::Range(path, range_error),
+            None => FgdbJsonError::Invalid(path, e.into_inner()),
+        }
+    })?;
+
+    // Reject trailing characters as serde_json::from_reader does.
+    deserializer.end().map_err(|e| FgdbJsonError::Invalid(".".to_string(), e))?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::rows::exchange_prices_row::ExchangePricesRow;
+    use crate::types::font_size::FontSize;
+    use crate::types::gem_level::GemLevel;
+    use crate::types::icon_size::IconSize;
+    use crate::types::item_level::ItemLevel;
+    use crate::types::price::Price;
+    use crate::types::sound_volume::SoundVolume;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_from_str_works() {
+        let rows: Vec<ExchangePricesRow> = from_str(r#"[{"base_type": "Chaos Orb", "price": {"price": 1.0}}]"#).unwrap();
+        assert_eq!(Price::new(1.0).unwrap(), rows[0].price);
+    }
+
+    #[test]
+    fn test_from_str_reports_path_of_range_error() {
+        let result: Result<Vec<ExchangePricesRow>, _> =
+            from_str(r#"[{"base_type": "Chaos Orb", "price": {"price": 1.0}}, {"base_type": "Divine Orb", "price": {"price": -5.0}}]"#);
+        match result {
+            Err(FgdbJsonError::Range(path, FgdbRangeError::Price(price))) => {
+                assert_eq!("[1].price", path);
+                assert_eq!(-5.0, price);
+            }
+            _ => panic!("Expected range error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_from_str_reports_range_errors_of_constrained_types() {
+        assert!(matches!(from_str::<Price>("-1.5"), Err(FgdbJsonError::Range(_, FgdbRangeError::Price(price))) if price == -1.5));
+        assert!(matches!(from_str::<FontSize>(r#"{"size": 60}"#), Err(FgdbJsonError::Range(_, FgdbRangeError::FontSize(_, _, 60)))));
+        assert!(matches!(from_str::<GemLevel>(r#"{"level": 0}"#), Err(FgdbJsonError::Range(_, FgdbRangeError::GemLevel(_, _, 0)))));
+        assert!(matches!(from_str::<ItemLevel>("101"), Err(FgdbJsonError::Range(_, FgdbRangeError::ItemLevel(_, _, 101)))));
+        assert!(matches!(from_str::<IconSize>(r#"{"size": 9}"#), Err(FgdbJsonError::Range(_, FgdbRangeError::IconSize(_, _, 9)))));
+        assert!(matches!(from_str::<SoundVolume>("500"), Err(FgdbJsonError::Range(_, FgdbRangeError::SoundVolume(_, _, 500)))));
+    }
+
+    #[test]
+    fn test_from_str_ignores_range_error_tags_in_values() {
+        let result = from_str::<Vec<ExchangePricesRow>>(r##"[{"base_type": "Chaos Orb", "price": "#range#{\"Price\":-1.0}#range#"}]"##);
+        assert!(matches!(result, Err(FgdbJsonError::Invalid(..))));
+    }
+
+    #[test]
+    fn test_from_str_reports_path_of_invalid_json() {
+        let result: Result<Vec<ExchangePricesRow>, _> = from_str(r#"[{"base_type": "Chaos Orb", "price": {"price": "free"}}]"#);
+        assert!(matches!(result, Err(FgdbJsonError::Invalid(path, _)) if path == "[0].price.price"));
+    }
+
+    #[test]
+    fn test_from_str_accepts_flat_and_nested_newtypes() {
+        let rows: Vec<ExchangePricesRow> = from_str(r#"[{"base_type": "Chaos Orb", "price": 1.0}, {"base_type": "Divine Orb", "price": {"price": 2.0}}]"#).unwrap();
+        assert_eq!(Price::new(1.0).unwrap(), rows[0].price);
+        assert_eq!(Price::new(2.0).unwrap(), rows[1].price);
+
+        let result: Result<Vec<ExchangePricesRow>, _> = from_str(r#"[{"base_type": "Chaos Orb", "price": {"cost": 1.0}}]"#);
+        assert!(matches!(result, Err(FgdbJsonError::Invalid(..))));
+
+        // Range errors in the nested representation report the inner field.
+        let result: Result<Vec<ExchangePricesRow>, _> = from_str(r#"[{"base_type": "Chaos Orb", "price": {"price": -1.0}}]"#);
+        assert!(matches!(result, Err(FgdbJsonError::Range(path, FgdbRangeError::Price(_))) if path == "[0].price"));
+    }
+
+    #[test]
+    fn test_to_string_restores_format_version_after_panic() {
+        struct Panics;
+        impl Serialize for Panics {
+            fn serialize<S: Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+                panic!("serialize");
+            }
+        }
+        assert!(std::panic::catch_unwind(|| to_string(&Panics, FormatVersion::Flat)).is_err());
+        assert_eq!(FormatVersion::Nested, FORMAT_VERSION.get());
+    }
+
+    #[test]
+    fn test_to_string_works() {
+        let price = Price::new(3.5).unwrap();
+        assert_eq!("{\n  \"price\": 3.5\n}", to_string(&price, FormatVersion::Nested).unwrap());
+        assert_eq!("3.5", to_string(&price, FormatVersion::Flat).unwrap());
+        let rows: Vec<ExchangePricesRow> = from_str(&to_string(&vec![ExchangePricesRow { base_type: "Chaos Orb".to_string(), price }], FormatVersion::Flat).unwrap()).unwrap();
+        assert_eq!(price, rows[0].price);
+    }
+
+    #[test]
+    fn test_to_string_reports_path_of_failure() {
+        let value = vec![BTreeMap::from([("keys", BTreeMap::from([((1, 2), 3)]))])];
+        let result = to_string(&value, FormatVersion::Nested);
+        assert!(matches!(result, Err(FgdbError::Json(FgdbJsonError::Invalid(path, _))) if path == "[0].keys"));
+    }
+}
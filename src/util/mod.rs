@@ -2,9 +2,13 @@
 // This software is licensed under the terms of the MIT License.
 // Created by Hankinsohl on 1/15/2026.
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "types"))]
 pub mod consts;
 #[cfg(feature = "std")]
 pub mod env;
 #[cfg(any(feature = "std", feature = "errors"))]
 pub mod errors;
+#[cfg(feature = "std")]
+pub mod http;
+#[cfg(any(feature = "std", feature = "types"))]
+pub mod json;